@@ -0,0 +1,105 @@
+//! Generates the in-process solution registry used by the `advent_of_code` binary.
+//! Days listed in `[package.metadata.advent_of_code] in_process` of `Cargo.toml` are included as modules,
+//! so `all`, `time` and `solve` can call them directly. All other days keep running as separate binaries,
+//! which means a day that does not build only breaks its own binary.
+//! Also exposes the toolchain, profile and features of the build, which are stored alongside timings.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_path = Path::new(&manifest_dir).join("Cargo.toml");
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let manifest = fs::read_to_string(&manifest_path).unwrap_or_default();
+
+    let mut days: Vec<(u8, String)> = in_process_days(&manifest)
+        .into_iter()
+        .filter_map(|day| {
+            let path = bin_dir.join(format!("{day:02}.rs"));
+            let source = fs::read_to_string(&path).ok();
+
+            if source.is_some_and(|source| source.contains("solution!(")) {
+                Some((day, path.to_str()?.to_string()))
+            } else {
+                println!(
+                    "cargo:warning=day {day} is listed in `in_process`, but `src/bin/{day:02}.rs` does not use `solution!`."
+                );
+                None
+            }
+        })
+        .collect();
+
+    days.sort_unstable();
+    days.dedup_by_key(|(day, _)| *day);
+
+    let mut out = String::new();
+
+    for (day, path) in &days {
+        out.push_str(&format!("#[path = {path:?}]\nmod day_{day:02};\n\n"));
+    }
+
+    out.push_str("pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for (day, _) in &days {
+        out.push_str(&format!(
+            "    advent_of_code::template::runner::Solution {{ day: day_{day:02}::DAY, run: day_{day:02}::run }},\n"
+        ));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+
+    emit_build_environment(&manifest);
+}
+
+/// Parses `in_process = [1, 2, ...]` from the `[package.metadata.advent_of_code]` section of the manifest.
+fn in_process_days(manifest: &str) -> Vec<u8> {
+    let Some(section) = manifest
+        .split_once("[package.metadata.advent_of_code]")
+        .and_then(|(_, rest)| rest.split("\n[").next())
+    else {
+        return vec![];
+    };
+
+    section
+        .split_once("in_process")
+        .and_then(|(_, rest)| rest.split_once('['))
+        .and_then(|(_, rest)| rest.split_once(']'))
+        .map(|(list, _)| {
+            list.split(',')
+                .filter_map(|day| day.trim().parse().ok())
+                .filter(|day| (1..=25).contains(day))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn emit_build_environment(manifest: &str) {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    if let Ok(output) = Command::new(rustc).arg("--version").output() {
+        let version = String::from_utf8_lossy(&output.stdout);
+        println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version.trim());
+    }
+
+    println!(
+        "cargo:rustc-env=AOC_PROFILE={}",
+        env::var("PROFILE").unwrap_or_default()
+    );
+
+    // features are only visible as `CARGO_FEATURE_<NAME>`, restore their spelling from the manifest.
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .map(|feature| {
+            manifest
+                .lines()
+                .filter_map(|line| line.split_once('=').map(|(name, _)| name.trim()))
+                .find(|name| name.replace('-', "_").eq_ignore_ascii_case(&feature))
+                .map_or(feature.clone(), str::to_string)
+        })
+        .collect();
+    features.sort_unstable();
+
+    println!("cargo:rustc-env=AOC_FEATURES={}", features.join(","));
+}
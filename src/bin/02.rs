@@ -1,6 +1,6 @@
 use std::{collections::HashSet, process::exit, u64};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse: parse);
 
 fn parse(input: &str) -> Vec<(u64, u64)> {
     input
@@ -133,12 +133,12 @@ fn sum_all(a: u64, b: u64) -> u64 {
     return sum;
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(parse(input).iter().map(|&(a, b)| sum_range(a, b)).sum())
+pub fn part_one(ranges: &[(u64, u64)]) -> Option<u64> {
+    Some(ranges.iter().map(|&(a, b)| sum_range(a, b)).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(parse(input).iter().map(|&(a, b)| sum_all(a, b)).sum())
+pub fn part_two(ranges: &[(u64, u64)]) -> Option<u64> {
+    Some(ranges.iter().map(|&(a, b)| sum_all(a, b)).sum())
 }
 
 #[cfg(test)]
@@ -147,13 +147,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(4174379265));
     }
 
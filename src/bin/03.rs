@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, parse: parse);
 
 pub fn parse(input: &str) -> Vec<Vec<u64>> {
     input
@@ -72,8 +72,8 @@ fn bank_joltage(batteries: &[u64]) -> u64 {
     }) as u64
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    Some(parse(input).iter().map(|bank| bank_joltage(bank)).sum())
+pub fn part_one(banks: &[Vec<u64>]) -> Option<u64> {
+    Some(banks.iter().map(|bank| bank_joltage(bank)).sum())
 }
 
 const SELECT_DIGITS: usize = 12;
@@ -109,13 +109,8 @@ fn p(i: usize, j: usize, batteries: &[u64], dp: &mut State) -> u64 {
     u64::max(dp[i - 1][j], this_bat + 10 * dp[i - 1][j - 1])
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    Some(
-        parse(input)
-            .iter()
-            .map(|bank| bank_joltage_large(bank))
-            .sum(),
-    )
+pub fn part_two(banks: &[Vec<u64>]) -> Option<u64> {
+    Some(banks.iter().map(|bank| bank_joltage_large(bank)).sum())
 }
 
 #[cfg(test)]
@@ -124,13 +119,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(357));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(3121910778619));
     }
 
@@ -144,6 +141,14 @@ mod tests {
         assert_eq!(bank_joltage(&[4, 1, 2, 6, 5, 3, 1, 8]), 68);
     }
     #[test]
+    fn test_joltage_large_allocations() {
+        // the DP table is the only allocation.
+        let joltage = advent_of_code::assert_allocations!(max = 1, || {
+            bank_joltage_large(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1])
+        });
+        assert_eq!(joltage, 987654321111);
+    }
+    #[test]
     fn test_joltage_large() {
         assert_eq!(
             bank_joltage_large(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]),
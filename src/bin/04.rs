@@ -10,7 +10,7 @@ use aoc_utils::{
 };
 use pheap::PairingHeap;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, variants: { part_two: [bad_queue => part_two_bad_queue] });
 
 type Tile = bool;
 
@@ -1,9 +1,9 @@
 use std::{cmp::Ordering, fmt::Display};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse: parse);
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-struct Interval {
+pub struct Interval {
     low: usize,
     high: usize,
 }
@@ -34,7 +34,7 @@ impl Display for Interval {
 fn parse(input: &str) -> (Vec<Interval>, Vec<usize>) {
     let (first, second) = input.split_once("\n\n").expect("Expect two input portions");
 
-    let intervals = first
+    let intervals: Vec<Interval> = first
         .split_ascii_whitespace()
         .map(|line| {
             let (low, high) = line
@@ -44,12 +44,13 @@ fn parse(input: &str) -> (Vec<Interval>, Vec<usize>) {
         })
         .collect();
 
-    let nums = second
+    let mut nums: Vec<usize> = second
         .split_ascii_whitespace()
         .map(|x| x.parse().expect("expect second half numbers"))
         .collect();
+    nums.sort_unstable();
 
-    (intervals, nums)
+    (merge_ranges(intervals), nums)
 }
 
 // fn merge_ranges(mut intervals: Vec<Interval>) -> Vec<Interval> {
@@ -117,17 +118,11 @@ fn is_single(intervals: &Vec<Interval>, num: usize) -> bool {
     intervals.iter().any(|x| x.contains(num).is_eq())
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (mut intervals, mut nums) = parse(input);
-    let intervals = merge_ranges(intervals);
-    nums.sort_unstable();
-    Some(count_numbers_in_range(&intervals, &nums))
+pub fn part_one((intervals, nums): &(Vec<Interval>, Vec<usize>)) -> Option<usize> {
+    Some(count_numbers_in_range(intervals, nums))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (mut intervals, _) = parse(input);
-    let intervals = merge_ranges(intervals);
-
+pub fn part_two((intervals, _): &(Vec<Interval>, Vec<usize>)) -> Option<usize> {
     Some(intervals.iter().map(|i| i.high + 1 - i.low).sum())
 }
 
@@ -137,13 +132,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(14));
     }
 
@@ -189,6 +189,14 @@ mod tests {
         assert_eq!(result, Some(40));
     }
 
+    #[test]
+    fn test_simulate_beams_allocations() {
+        // `beams` and `next_beams` are swapped between levels, so only their growth allocates.
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = advent_of_code::assert_allocations!(max = 5, || simulate_beams(input));
+        assert_eq!(result.splits, 21);
+    }
+
     #[test]
     fn test_parse() {
         let map = "\
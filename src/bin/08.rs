@@ -11,7 +11,7 @@ use aoc_utils::{
 };
 use lina::{Point2, Vec2, Vec3};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse: parse);
 
 type JBox = Vec3<i64>;
 
@@ -41,17 +41,16 @@ fn parse(input: &str) -> Vec<JBox> {
 
 type AdjacencyList = Vec<Vec<usize>>;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(largest_circuits_from_shortest_connections(input, 1000))
+pub fn part_one(jboxes: &[JBox]) -> Option<usize> {
+    Some(largest_circuits_from_shortest_connections(jboxes, 1000))
 }
 
 pub fn largest_circuits_from_shortest_connections(
-    input: &str,
+    jboxes: &[JBox],
     no_shortest_connections: usize,
 ) -> usize {
     let now = Instant::now();
 
-    let jboxes = parse(input);
     let n = jboxes.len();
 
     let mut graph_matrix =
@@ -162,10 +161,8 @@ fn bfs(src: usize, graph: &AdjacencyList, colour: u32, colours: &mut Vec<u32>) {
     }
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let jboxes = parse(input);
-
-    let distances = find_distances(&jboxes);
+pub fn part_two(jboxes: &[JBox]) -> Option<usize> {
+    let distances = find_distances(jboxes);
 
     let n = jboxes.len();
     let mut union = UnionFind::new(n);
@@ -190,7 +187,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = largest_circuits_from_shortest_connections(
-            &advent_of_code::template::read_file("examples", DAY),
+            &parse(&advent_of_code::template::read_file("examples", DAY)),
             10,
         );
         assert_eq!(result, 40);
@@ -198,7 +195,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(25272));
     }
 }
@@ -5,7 +5,7 @@ use aoc_utils::ResultExt;
 /// I think the orientation i.e. knowing that the right side of the edge is always inside is needed as the edge may cut through the rectangle if it is on the edge of the rectangle.
 use lina::{Point2, Vec2};
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, parse: parse);
 
 type P = Point2<usize>;
 
@@ -20,9 +20,7 @@ fn parse(input: &str) -> Vec<P> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let points = parse(input);
-
+pub fn part_one(points: &[P]) -> Option<usize> {
     points
         .iter()
         .enumerate()
@@ -32,10 +30,8 @@ pub fn part_one(input: &str) -> Option<usize> {
         .max()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let points = parse(input);
-
-    let edges = find_edges(&points);
+pub fn part_two(points: &[P]) -> Option<usize> {
+    let edges = find_edges(points);
 
     let edge_set = EdgeSet::new(&edges);
 
@@ -52,7 +48,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     max
 }
 
-fn find_edges(points: &Vec<P>) -> Vec<Edge> {
+fn find_edges(points: &[P]) -> Vec<Edge> {
     let mut edges = vec![];
     for ps in points
         .windows(2)
@@ -246,13 +242,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(50));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(24));
     }
 
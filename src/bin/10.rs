@@ -13,7 +13,7 @@ use microlp::{LinearExpr, OptimizationDirection, Problem};
 use nalgebra::{DMatrix as Matrix, DVector};
 use num::{Integer, Signed, integer::lcm};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse: parse, variants: { part_two: [ilp => part_two_ilp] });
 
 fn parse(input: &str) -> Vec<Machine> {
     input
@@ -84,8 +84,7 @@ fn generate_sequences(max_len: usize) -> Vec<Vec<usize>> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let machines = parse(input);
+pub fn part_one(machines: &[Machine]) -> Option<usize> {
     let max_buttons = machines.iter().map(|x| x.buttons.len()).max().unwrap_or(0);
     let ordered_iterations = generate_sequences(max_buttons);
     Some(
@@ -96,8 +95,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let machines = parse(input);
+pub fn part_two(machines: &[Machine]) -> Option<usize> {
     let max_buttons = machines.iter().map(|x| x.buttons.len()).max().unwrap_or(0);
     let ordered_iterations = generate_sequences(max_buttons);
 
@@ -109,8 +107,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(ans)
 }
 
-pub fn part_two_ilp(input: &str) -> Option<usize> {
-    let machines = parse(input);
+pub fn part_two_ilp(machines: &[Machine]) -> Option<usize> {
     let sum = machines
         .iter()
         .map(|x| Equation::from_machine(x))
@@ -122,7 +119,7 @@ pub fn part_two_ilp(input: &str) -> Option<usize> {
 type Joltage = ArrayVec<usize, 10>;
 
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     goal_size: usize,
     goal: usize,
     buttons: ArrayVec<usize, 20>,
@@ -490,13 +487,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(7));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(33));
     }
 
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, str::FromStr};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse: parse);
 
 fn count_paths(graph: &Graph, src: usize, out: usize) -> usize {
     let mut p = vec![vec![0; graph.size]];
@@ -38,17 +38,17 @@ fn count_paths(graph: &Graph, src: usize, out: usize) -> usize {
     ans
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let graph: Graph = input.parse().unwrap();
-
-    Some(count_paths(&graph, graph.you, graph.out))
+fn parse(input: &str) -> Graph {
+    input.parse().unwrap()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let graph: Graph = input.parse().unwrap();
+pub fn part_one(graph: &Graph) -> Option<usize> {
+    Some(count_paths(graph, graph.you, graph.out))
+}
 
-    let dac_fft = count_paths(&graph, graph.dac, graph.fft);
-    let fft_dac = count_paths(&graph, graph.fft, graph.dac);
+pub fn part_two(graph: &Graph) -> Option<usize> {
+    let dac_fft = count_paths(graph, graph.dac, graph.fft);
+    let fft_dac = count_paths(graph, graph.fft, graph.dac);
 
     let (first, second) = if dac_fft != 0 {
         (graph.dac, graph.fft)
@@ -56,16 +56,16 @@ pub fn part_two(input: &str) -> Option<usize> {
         (graph.fft, graph.dac)
     };
 
-    let first_leg = count_paths(&graph, graph.svr, first);
+    let first_leg = count_paths(graph, graph.svr, first);
     let middle_leg = fft_dac + dac_fft;
-    let last_leg =count_paths(&graph, second, graph.out);
+    let last_leg =count_paths(graph, second, graph.out);
     let count = first_leg * middle_leg * last_leg;
 
     Some(count)
 }
 
 #[derive(Debug)]
-struct Graph {
+pub struct Graph {
     you: usize,
     out: usize,
     dac: usize,
@@ -145,13 +145,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(0));
     }
 }
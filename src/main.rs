@@ -1,19 +1,45 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, scaffold, solve, stars, time, verify,
+};
+use advent_of_code::template::runner::RunOptions;
+use args::{AppArguments, parse};
+use solutions::SOLUTIONS;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
+
+/// Solutions that are compiled into this binary, see `build.rs`.
+mod solutions {
+    // every day binary declares a dhat allocator, only one may exist per binary.
+    #[cfg(not(feature = "dhat-heap"))]
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+    #[cfg(feature = "dhat-heap")]
+    pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];
+}
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day,
+        commands::time,
+        export::ExportFormat,
+        runner::{DEFAULT_TIMEOUT, InputSource},
+    };
+    use std::{num::NonZeroUsize, path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        Examples {
+            day: Day,
+        },
+        Leaderboard {
+            id: Option<u64>,
+            file: Option<PathBuf>,
+            offline: bool,
+            day: Option<Day>,
+        },
         Read {
             day: Day,
         },
@@ -26,18 +52,59 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            watch: bool,
             submit: Option<u8>,
+            variant: Option<String>,
+            variants: bool,
+            timeout: Option<Duration>,
+            input: InputSource,
         },
         All {
             release: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        TimeHistory {
+            day: Option<Day>,
+        },
+        TimeCompare {
+            day: Option<Day>,
+            threshold: f64,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        TimeMemory {
+            day: Option<Day>,
+            store: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        TimeExport {
+            format: ExportFormat,
+            out: Option<PathBuf>,
+            all: bool,
+            day: Option<Day>,
+            timeout: Option<Duration>,
+            jobs: usize,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            variants: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
         },
+        Verify {
+            accept: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        Stars,
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -46,20 +113,81 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                timeout: parse_timeout(&mut args, Some(DEFAULT_TIMEOUT))?,
+                jobs: parse_jobs(&mut args)?.unwrap_or(1),
             },
-            Some("time") => {
-                let all = args.contains("--all");
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                day: args.opt_free_from_str()?,
+            },
+            Some("time") if args.contains("--compare") => {
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(time::DEFAULT_COMPARE_THRESHOLD);
+                let timeout = parse_timeout(&mut args, Some(DEFAULT_TIMEOUT))?;
+                let jobs = parse_jobs(&mut args)?.unwrap_or(1);
+
+                AppArguments::TimeCompare {
+                    day: args.opt_free_from_str()?,
+                    threshold,
+                    timeout,
+                    jobs,
+                }
+            }
+            Some("time") if args.contains("--memory") => {
                 let store = args.contains("--store");
+                let timeout = parse_timeout(&mut args, Some(DEFAULT_TIMEOUT))?;
+                let jobs = parse_jobs(&mut args)?.unwrap_or(1);
 
-                AppArguments::Time {
-                    all,
+                AppArguments::TimeMemory {
                     day: args.opt_free_from_str()?,
                     store,
+                    timeout,
+                    jobs,
+                }
+            }
+            Some("time") => {
+                let export = args.opt_value_from_str("--export")?;
+                let all = args.contains("--all");
+                let timeout = parse_timeout(&mut args, Some(DEFAULT_TIMEOUT))?;
+                // timed runs stay serial unless asked otherwise, parallel runs skew the timings.
+                let jobs = parse_jobs(&mut args)?.unwrap_or(1);
+
+                match export {
+                    Some(format) => AppArguments::TimeExport {
+                        format,
+                        out: args.opt_value_from_str("--out")?,
+                        all,
+                        day: args.opt_free_from_str()?,
+                        timeout,
+                        jobs,
+                    },
+                    None => {
+                        let store = args.contains("--store");
+                        let variants = args.contains("--variants");
+
+                        AppArguments::Time {
+                            all,
+                            day: args.opt_free_from_str()?,
+                            store,
+                            variants,
+                            timeout,
+                            jobs,
+                        }
+                    }
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                file: args.opt_value_from_str("--file")?,
+                offline: args.contains("--offline"),
+                day: args.opt_value_from_str("--day")?,
+                id: args.opt_free_from_str()?,
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
@@ -73,9 +201,26 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                variant: args.opt_value_from_str("--variant")?,
+                variants: args.contains("--variants"),
+                timeout: parse_timeout(&mut args, None)?,
+                input: InputSource::from_flags(
+                    args.contains("--example"),
+                    args.opt_value_from_str("--example-part")?,
+                    args.opt_value_from_str("--input")?,
+                )?,
             },
+            Some("verify") => AppArguments::Verify {
+                accept: args.contains("--accept"),
+                timeout: parse_timeout(&mut args, None)?,
+                jobs: parse_jobs(&mut args)?.unwrap_or(1),
+            },
+            Some("stars") => AppArguments::Stars,
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +238,31 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse `--jobs N`. `--jobs 0` uses one job per available CPU.
+    fn parse_jobs(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_value_from_str::<_, usize>("--jobs")?
+            .map(|jobs| match jobs {
+                0 => std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+                x => x,
+            }))
+    }
+
+    /// Parse `--timeout SECONDS`, the wall-clock limit for a part including its benchmark.
+    /// Without the flag, `default` applies. `--timeout 0` runs without a limit.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+        default: Option<Duration>,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        Ok(match args.opt_value_from_str::<_, f64>("--timeout")? {
+            Some(seconds) if seconds <= 0.0 => None,
+            Some(seconds) => Some(Duration::from_secs_f64(seconds)),
+            None => default,
+        })
+    }
 }
 
 fn main() {
@@ -102,9 +272,48 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                timeout,
+                jobs,
+            } => all::handle(SOLUTIONS, release, timeout, jobs),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                variants,
+                timeout,
+                jobs,
+            } => time::handle(SOLUTIONS, day, all, store, variants, timeout, jobs),
+            AppArguments::TimeHistory { day } => time::handle_history(day),
+            AppArguments::TimeCompare {
+                day,
+                threshold,
+                timeout,
+                jobs,
+            } => time::handle_compare(SOLUTIONS, day, threshold, timeout, jobs),
+            AppArguments::TimeMemory {
+                day,
+                store,
+                timeout,
+                jobs,
+            } => time::handle_memory(SOLUTIONS, day, store, timeout, jobs),
+            AppArguments::TimeExport {
+                format,
+                out,
+                all,
+                day,
+                timeout,
+                jobs,
+            } => time::handle_export(SOLUTIONS, format, out, day, all, timeout, jobs),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Examples { day } => examples::handle(day),
+            AppArguments::Leaderboard {
+                id,
+                file,
+                offline,
+                day,
+            } => leaderboard::handle(id, file, offline, day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
@@ -120,25 +329,35 @@ fn main() {
                 day,
                 release,
                 dhat,
+                watch,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                variant,
+                variants,
+                timeout,
+                input,
+            } => solve::handle(
+                SOLUTIONS,
+                day,
+                release,
+                dhat,
+                watch,
+                &RunOptions {
+                    submit,
+                    variant,
+                    compare_variants: variants,
+                    timeout,
+                    input,
+                    ..RunOptions::default()
+                },
+            ),
+            AppArguments::Verify {
+                accept,
+                timeout,
+                jobs,
+            } => verify::handle(SOLUTIONS, accept, timeout, jobs),
+            AppArguments::Stars => stars::handle(),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
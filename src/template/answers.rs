@@ -0,0 +1,240 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Outcome of comparing a result with the accepted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Mismatch { expected: String },
+    Unknown,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// A file that cannot be parsed is an error, so that a broken file is not mistaken for having no answers.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(contents) => Answers::try_from(contents)
+                .map_err(|e| format!("could not parse \"{ANSWERS_FILE_PATH}\": {e}")),
+            Err(_) => Ok(Answers::default()),
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the accepted answer for a part, replacing a previous one.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Compare a result with the accepted answer. A missing result only counts as a mismatch if an answer is known.
+    pub fn verify(&self, day: Day, part: u8, result: Option<&str>) -> Verdict {
+        match (self.get(day, part), result) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(result)) if expected.trim() == result.trim() => Verdict::Correct,
+            (Some(expected), _) => Verdict::Mismatch {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+    use crate::day;
+
+    fn get_mock_answers() -> Answers {
+        let json = r#"{ "data": [{ "day": "04", "part_1": "13", "part_2": null }] }"#;
+        Answers::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.get(day!(4), 1), Some("13"));
+        assert_eq!(answers.get(day!(4), 2), None);
+        assert_eq!(answers.get(day!(5), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "04" }] }"#;
+        Answers::try_from(json.to_string()).unwrap();
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verify(day!(4), 1, Some("13")), Verdict::Correct);
+        assert_eq!(
+            answers.verify(day!(4), 1, Some("14")),
+            Verdict::Mismatch {
+                expected: "13".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(4), 1, None),
+            Verdict::Mismatch {
+                expected: "13".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(4), 2, Some("43")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(2), 2, "7");
+        answers.set(day!(4), 2, "43");
+        assert_eq!(answers.data[0].day, day!(2));
+        assert_eq!(answers.get(day!(2), 2), Some("7"));
+        assert_eq!(answers.get(day!(4), 2), Some("43"));
+    }
+}
@@ -0,0 +1,388 @@
+/// Client for the Advent of Code website, used to download puzzles and inputs and to submit answers.
+/// The session cookie is read from the `ADVENT_OF_CODE_SESSION` environment variable or from `~/.adventofcode.session`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::template::submissions::Submissions;
+use crate::template::{Day, puzzle_markdown};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+const SESSION_FILE: &str = ".adventofcode.session";
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    /// The server could not be reached.
+    Request(String),
+    /// The server responded with a non-success status code.
+    Status(u16),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `{SESSION_ENV}` or paste it into `~/{SESSION_FILE}`."
+            ),
+            AocClientError::YearNotSet => {
+                write!(f, "`AOC_YEAR` is not set, set it in `.cargo/config.toml`.")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::Status(400) => {
+                write!(
+                    f,
+                    "the server rejected the request, the session cookie may have expired."
+                )
+            }
+            AocClientError::Status(404) => {
+                write!(f, "not found, the puzzle may not be unlocked yet.")
+            }
+            AocClientError::Status(code) => write!(f, "the server responded with status {code}."),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl AocClientError {
+    /// Whether the request might succeed if it is repeated, e.g. when the server is overloaded right after a puzzle unlocked.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            AocClientError::Request(_) => true,
+            AocClientError::Status(code) => *code == 404 || *code >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, _) => AocClientError::Status(code),
+            ureq::Error::Transport(e) => AocClientError::Request(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Create a client for `AOC_YEAR`. The base URL can be overridden with `AOC_BASE_URL`, e.g. for a local stand-in server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &read_session()?, year))
+    }
+
+    /// The puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}/input", self.year, day.into_inner()))
+    }
+
+    /// The HTML page of a day's puzzle. Contains the description of part two once part one is solved.
+    pub fn puzzle_html(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}", self.year, day.into_inner()))
+    }
+
+    /// The JSON of a private leaderboard, `id` is the number at the end of its URL.
+    pub fn leaderboard(&self, id: u64) -> Result<String, AocClientError> {
+        self.get(&format!(
+            "/{}/leaderboard/private/view/{id}.json",
+            self.year
+        ))
+    }
+
+    /// Post an answer and return the message of the response as markdown.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        );
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(puzzle_markdown::articles(&response.into_string()?))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+fn read_session() -> Result<String, AocClientError> {
+    let session = match env::var(SESSION_ENV) {
+        Ok(session) => session,
+        Err(_) => home_dir()
+            .and_then(|home| fs::read_to_string(home.join(SESSION_FILE)).ok())
+            .ok_or(AocClientError::SessionNotFound)?,
+    };
+
+    if session.trim().is_empty() {
+        return Err(AocClientError::SessionNotFound);
+    }
+
+    Ok(session)
+}
+
+/// The `User-Agent` of requests, `AOC_USER_AGENT` if set. Advent of Code asks automated tools to name a way to
+/// contact their author, e.g. `github.com/<you>/<repo> by <email>`.
+fn user_agent() -> String {
+    env::var(USER_AGENT_ENV)
+        .ok()
+        .filter(|agent| !agent.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.into())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Print the puzzle description of a day from `data/puzzles`.
+/// It is only fetched if it was not downloaded yet, or if part one was solved since and part two is missing.
+pub fn read(day: Day) -> Result<(), AocClientError> {
+    let puzzle = match fs::read_to_string(get_puzzle_path(day)) {
+        Err(_) => fetch_puzzle(day)?,
+        Ok(cached) if !puzzle_markdown::has_part_two(&cached) && is_part_two_unlocked(day) => {
+            fetch_puzzle(day).unwrap_or_else(|e| {
+                eprintln!("Failed to fetch part two, showing the saved description: {e}");
+                // save the description again, so that the fetch is not retried on every read.
+                let _ = write_file(&get_puzzle_path(day), &cached);
+                cached
+            })
+        }
+        Ok(cached) => cached,
+    };
+
+    print!("{}", puzzle_markdown::to_terminal(&puzzle));
+    Ok(())
+}
+
+fn fetch_puzzle(day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = puzzle_markdown::articles(&client.puzzle_html(day)?);
+    write_file(&get_puzzle_path(day), &puzzle)?;
+    Ok(puzzle)
+}
+
+/// Whether part two was unlocked since the description was saved, i.e. part one was accepted after it.
+/// A description saved after a fetch, successful or not, is not fetched again until `cargo download`.
+fn is_part_two_unlocked(day: Day) -> bool {
+    let Some(solved_at) = Submissions::read_from_file()
+        .ok()
+        .and_then(|submissions| submissions.solved_at(day, 1))
+    else {
+        return false;
+    };
+
+    fs::metadata(get_puzzle_path(day))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .is_none_or(|saved_at| saved_at.as_secs() < solved_at)
+}
+
+/// Download the puzzle input and description of a day to `data/inputs` and `data/puzzles`.
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    let puzzle = puzzle_markdown::articles(&client.puzzle_html(day)?);
+    let input = client.input(day)?;
+
+    write_file(&puzzle_path, &puzzle)?;
+    write_file(&input_path, &input)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submit an answer for the configured year and print the response.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<String, AocClientError> {
+    let message = AocClient::from_env()?.submit(day, part, result)?;
+    println!("{message}");
+    Ok(message)
+}
+
+pub(crate) fn write_file(path: &str, contents: &str) -> io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_input_path(day: Day) -> String {
+    format!("data/inputs/{day}.txt")
+}
+
+fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+pub fn get_year() -> Option<u16> {
+    match std::env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok().or(None),
+        Err(_) => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, user_agent};
+    use crate::day;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// A stand-in for adventofcode.com that answers each request with the next canned response.
+    /// Returns the base URL and a handle to the requests it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some((_, length)) = line.to_lowercase().split_once("content-length:")
+                        {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+
+                    let mut body_bytes = vec![0; content_length];
+                    reader.read_exact(&mut body_bytes).unwrap();
+                    request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_inputs_and_puzzles() {
+        let (base_url, server) = serve(vec![
+            (200, "3   4\n4   3\n"),
+            (
+                200,
+                "<main><article><h2>--- Day 1 ---</h2></article></main>",
+            ),
+        ]);
+
+        let client = AocClient::new(&base_url, "abc123\n", 2024);
+        assert_eq!(client.input(day!(1)).unwrap(), "3   4\n4   3\n");
+        assert!(client.puzzle_html(day!(1)).unwrap().contains("Day 1"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].contains(&format!("User-Agent: {}\r\n", user_agent())));
+        assert!(requests[1].starts_with("GET /2024/day/1 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        )]);
+
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert_eq!(
+            client.submit(day!(12), 2, "42").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/12/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_status_codes() {
+        let (base_url, server) = serve(vec![(404, "Not Found")]);
+
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert!(matches!(
+            client.input(day!(25)),
+            Err(AocClientError::Status(404))
+        ));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+/// Renders the stored timings as an SVG bar chart for the README.
+/// Runtimes are drawn on a log scale, so days that take nanoseconds and days that take seconds fit into one chart.
+use std::fmt::Write;
+
+use crate::template::timings::{PartTiming, Timings};
+
+const WIDTH: f64 = 800.0;
+const LABEL_WIDTH: f64 = 70.0;
+const MARGIN: f64 = 20.0;
+const LEGEND_HEIGHT: f64 = 30.0;
+const AXIS_HEIGHT: f64 = 30.0;
+const BAR_HEIGHT: f64 = 8.0;
+const BAR_GAP: f64 = 2.0;
+const GROUP_GAP: f64 = 12.0;
+
+/// Decades shown when there is nothing to scale the axis to, 1µs to 1ms.
+const DEFAULT_DECADES: (i32, i32) = (3, 6);
+
+const PARTS: [(&str, &str); 3] = [
+    ("Parse", "#9e9e9e"),
+    ("Part 1", "#4c8eda"),
+    ("Part 2", "#f2a93b"),
+];
+
+/// Render a log-scale bar chart with one group per day and one bar per part.
+pub fn render(timings: &Timings) -> String {
+    let (lo, hi) = decade_range(timings);
+    let plot_width = WIDTH - LABEL_WIDTH - MARGIN;
+    let x = |nanos: f64| {
+        let position = (nanos.max(1.0).log10() - f64::from(lo)) / f64::from(hi - lo);
+        LABEL_WIDTH + position.clamp(0.0, 1.0) * plot_width
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let group_height = PARTS.len() as f64 * (BAR_HEIGHT + BAR_GAP) + GROUP_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = timings.data.len() as f64 * group_height;
+    let height = LEGEND_HEIGHT + plot_height + AXIS_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{WIDTH}" height="{height}" fill="#ffffff"/>"##
+    );
+
+    // legend
+    let mut legend_x = LABEL_WIDTH;
+    for (name, color) in PARTS {
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{legend_x}" y="10" width="10" height="10" fill="{color}"/><text x="{}" y="19">{name}</text>"#,
+            legend_x + 14.0
+        );
+        legend_x += 80.0;
+    }
+
+    // grid lines and decade labels
+    let axis_y = LEGEND_HEIGHT + plot_height;
+    for decade in lo..=hi {
+        let grid_x = x(10_f64.powi(decade));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{grid_x:.1}" y1="{LEGEND_HEIGHT}" x2="{grid_x:.1}" y2="{axis_y}" stroke="#e0e0e0"/><text x="{grid_x:.1}" y="{}" text-anchor="middle" fill="#555555">{}</text>"##,
+            axis_y + 18.0,
+            decade_label(decade)
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let group_y = LEGEND_HEIGHT + i as f64 * group_height;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{:.1}" text-anchor="end">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            group_y + group_height / 2.0,
+            timing.day
+        );
+
+        let parts = [timing.parse, timing.part_1, timing.part_2];
+        for (j, (part, (name, color))) in parts.iter().zip(PARTS).enumerate() {
+            let Some(part) = part else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = group_y + j as f64 * (BAR_HEIGHT + BAR_GAP);
+            let _ = writeln!(
+                svg,
+                "{}",
+                bar(part, name, color, bar_y, x(part.median_nanos))
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn bar(part: &PartTiming, name: &str, color: &str, y: f64, end_x: f64) -> String {
+    format!(
+        r#"<rect x="{LABEL_WIDTH}" y="{y:.1}" width="{:.1}" height="{BAR_HEIGHT}" fill="{color}"><title>{name}: {part}</title></rect>"#,
+        (end_x - LABEL_WIDTH).max(1.0)
+    )
+}
+
+/// The powers of ten that enclose all timings, in nanoseconds.
+#[allow(clippy::cast_possible_truncation)]
+fn decade_range(timings: &Timings) -> (i32, i32) {
+    let values = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.parse, t.part_1, t.part_2])
+        .flatten()
+        .map(|t| t.median_nanos.max(1.0).log10());
+
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+
+    if min > max {
+        return DEFAULT_DECADES;
+    }
+
+    let lo = min.floor() as i32;
+    let hi = (max.ceil() as i32).max(lo + 1);
+    (lo, hi)
+}
+
+fn decade_label(decade: i32) -> String {
+    let (unit, exponent) = match decade {
+        ..3 => ("ns", decade),
+        3..6 => ("µs", decade - 3),
+        6..9 => ("ms", decade - 6),
+        _ => ("s", decade - 9),
+    };
+
+    format!("{}{unit}", 10_u64.pow(exponent.unsigned_abs()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decade_label, decade_range, render};
+    use crate::template::fixtures::{get_mock_timings, timing};
+    use crate::template::timings::Timings;
+
+    #[test]
+    fn scales_axis_to_enclosing_decades() {
+        let mut timings = get_mock_timings();
+        assert_eq!(decade_range(&timings), (7, 8));
+        timings.data[0].parse = timing(300.0);
+        assert_eq!(decade_range(&timings), (2, 8));
+        assert_eq!(decade_range(&Timings::default()), (3, 6));
+    }
+
+    #[test]
+    fn labels_decades() {
+        assert_eq!(decade_label(0), "1ns");
+        assert_eq!(decade_label(2), "100ns");
+        assert_eq!(decade_label(3), "1µs");
+        assert_eq!(decade_label(7), "10ms");
+        assert_eq!(decade_label(10), "10s");
+    }
+
+    #[test]
+    fn renders_a_bar_per_part() {
+        let svg = render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 5);
+        assert!(svg.contains(">Day 01</text>"));
+        assert!(svg.contains("<title>Part 1: 10.0ms</title>"));
+        assert!(svg.contains(">100ms</text>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert_eq!(svg.matches("<title>").count(), 0);
+        assert!(svg.contains(">1µs</text>"));
+    }
+}
@@ -1,5 +1,20 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::run_multi,
+    runner::{RunOptions, Solution},
+};
+
+pub fn handle(solutions: &[Solution], is_release: bool, timeout: Option<Duration>, jobs: usize) {
+    run_multi(
+        solutions,
+        &all_days().collect(),
+        is_release,
+        &RunOptions {
+            timeout,
+            ..RunOptions::default()
+        },
+        jobs,
+    );
 }
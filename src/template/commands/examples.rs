@@ -0,0 +1,192 @@
+use std::{fs, path::Path, process};
+
+use crate::template::Day;
+use crate::template::puzzle_examples::{self, PartExample};
+
+/// Write the examples of a day's puzzle description to `data/examples` and fill in the expected answers of its tests.
+/// Existing examples and tests that already expect an answer are left alone, so this can be run again once part two unlocked.
+pub fn handle(day: Day) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\", run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let examples = puzzle_examples::extract(&puzzle);
+    if examples.is_empty() {
+        eprintln!("\"{puzzle_path}\" does not contain a puzzle description.");
+        process::exit(1);
+    }
+
+    let module_path = format!("src/bin/{day}.rs");
+    let mut module = fs::read_to_string(&module_path).ok();
+    let mut is_module_changed = false;
+
+    for (path, input) in example_files(day, &examples) {
+        write_example(&path, input);
+    }
+
+    for (part, example) in (1..).zip(&examples) {
+        let example_part = example_part(&examples, part);
+        let example_path = example_path(day, example_part);
+        let has_example = fs::read_to_string(&example_path).is_ok_and(|s| !s.trim().is_empty());
+
+        if let PartExample {
+            answer: Some(answer),
+            ..
+        } = example
+            && has_example
+            && let Some(filled) = module
+                .as_deref()
+                .and_then(|m| puzzle_examples::fill_test(m, part, answer, example_part))
+        {
+            println!("Expecting {answer} for the example of part {part}.");
+            module = Some(filled);
+            is_module_changed = true;
+        }
+    }
+
+    if is_module_changed && let Some(module) = module {
+        if let Err(e) = fs::write(&module_path, module) {
+            eprintln!("Failed to update \"{module_path}\": {e}");
+            process::exit(1);
+        }
+        println!("Updated the tests in \"{module_path}\".");
+    }
+}
+
+/// The example files of a puzzle and their contents. Part one's example goes to `NN.txt` and part two's to `NN-2.txt`
+/// if it differs. Further examples follow as `NN-3.txt`, `NN-4.txt`, … in order, their tests are left to you.
+fn example_files(day: Day, examples: &[PartExample]) -> Vec<(String, &str)> {
+    let mut files: Vec<(String, &str)> = (1..)
+        .zip(examples)
+        .filter(|(part, _)| *part == 1 || example_part(examples, *part).is_some())
+        .filter_map(|(part, example)| {
+            let input = example.input.as_deref()?;
+            Some((example_path(day, example_part(examples, part)), input))
+        })
+        .collect();
+
+    let mut written: Vec<&str> = examples
+        .first()
+        .and_then(|example| example.input.as_deref())
+        .into_iter()
+        .collect();
+    let mut number = 3;
+
+    for input in examples.iter().flat_map(|example| &example.extra_inputs) {
+        if !written.contains(&input.as_str()) {
+            written.push(input);
+            files.push((format!("data/examples/{day}-{number}.txt"), input));
+            number += 1;
+        }
+    }
+
+    files
+}
+
+/// The example file that the test of `part` reads, `Some(part)` if the part has an example of its own.
+fn example_part(examples: &[PartExample], part: u8) -> Option<u8> {
+    let input = examples.get(usize::from(part) - 1)?.input.as_ref()?;
+    (part > 1 && examples[0].input.as_ref() != Some(input)).then_some(part)
+}
+
+fn example_path(day: Day, example_part: Option<u8>) -> String {
+    match example_part {
+        Some(part) => format!("data/examples/{day}-{part}.txt"),
+        None => format!("data/examples/{day}.txt"),
+    }
+}
+
+fn write_example(path: &str, input: &str) {
+    if fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty()) {
+        println!("Kept the existing example \"{path}\".");
+        return;
+    }
+
+    if let Some(dir) = Path::new(path).parent()
+        && let Err(e) = fs::create_dir_all(dir)
+    {
+        eprintln!("Failed to create \"{}\": {e}", dir.display());
+        process::exit(1);
+    }
+
+    match fs::write(path, input) {
+        Ok(()) => println!("Wrote example to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_files, example_part};
+    use crate::day;
+    use crate::template::puzzle_examples::PartExample;
+
+    fn example(input: Option<&str>, extra_inputs: &[&str]) -> PartExample {
+        PartExample {
+            input: input.map(Into::into),
+            extra_inputs: extra_inputs.iter().map(|&input| input.into()).collect(),
+            answer: None,
+        }
+    }
+
+    #[test]
+    fn writes_extra_examples_after_the_parts() {
+        let examples = [
+            example(Some("a"), &["b", "c"]),
+            example(Some("d"), &["c", "e"]),
+        ];
+
+        assert_eq!(
+            example_files(day!(7), &examples),
+            vec![
+                ("data/examples/07.txt".into(), "a"),
+                ("data/examples/07-2.txt".into(), "d"),
+                ("data/examples/07-3.txt".into(), "b"),
+                ("data/examples/07-4.txt".into(), "c"),
+                ("data/examples/07-5.txt".into(), "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_extra_examples_in_place_once_part_two_unlocks() {
+        let part_one = [example(Some("a"), &["b"])];
+        let both_parts = [example(Some("a"), &["b"]), example(Some("c"), &[])];
+
+        assert_eq!(
+            example_files(day!(7), &part_one)[1],
+            ("data/examples/07-3.txt".into(), "b")
+        );
+        assert_eq!(
+            example_files(day!(7), &both_parts)[2],
+            ("data/examples/07-3.txt".into(), "b")
+        );
+    }
+
+    #[test]
+    fn shares_the_example_of_part_one() {
+        let examples = [example(Some("a"), &[]), example(Some("a"), &["a"])];
+
+        assert_eq!(
+            example_files(day!(7), &examples),
+            vec![("data/examples/07.txt".into(), "a")]
+        );
+        assert_eq!(example_part(&examples, 1), None);
+        assert_eq!(example_part(&examples, 2), None);
+        assert_eq!(
+            example_part(&[example(Some("a"), &[]), example(None, &[])], 2),
+            None
+        );
+        assert_eq!(
+            example_part(&[example(Some("a"), &[]), example(Some("b"), &[])], 2),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::aoc_client::{self, AocClient, AocClientError};
+use crate::template::leaderboard::{self, CACHE_DURATION, Leaderboard};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day};
+
+/// Show a private leaderboard, either from `file` or by its `id`.
+/// Leaderboards are fetched at most once per [`CACHE_DURATION`], and never with `offline`.
+pub fn handle(id: Option<u64>, file: Option<PathBuf>, offline: bool, day: Option<Day>) {
+    let json = match (file, id) {
+        (Some(file), _) => fs::read_to_string(&file).unwrap_or_else(|e| {
+            eprintln!("Failed to read \"{}\": {e}", file.display());
+            process::exit(1);
+        }),
+        (None, Some(id)) => load(id, offline).unwrap_or_else(|e| {
+            eprintln!("Failed to fetch leaderboard {id}: {e}");
+            process::exit(1);
+        }),
+        (None, None) => {
+            eprintln!("Specify the id of a leaderboard, or a JSON file with `--file`.");
+            process::exit(1);
+        }
+    };
+
+    match Leaderboard::try_from(json) {
+        Ok(leaderboard) => print!("{}", leaderboard::render(&leaderboard, day)),
+        Err(e) => {
+            eprintln!("Failed to read leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn load(id: u64, offline: bool) -> Result<String, AocClientError> {
+    let path = leaderboard::cache_path(id);
+
+    match leaderboard::cache_age(&path) {
+        Some(age) if offline || age < CACHE_DURATION => {
+            println!(
+                "{ANSI_ITALIC}Cached {} minute(s) ago.{ANSI_RESET}",
+                age.as_secs() / 60
+            );
+            Ok(fs::read_to_string(&path)?)
+        }
+        None if offline => {
+            eprintln!("Leaderboard {id} was not cached yet, run without `--offline` to fetch it.");
+            process::exit(1);
+        }
+        _ => {
+            let json = AocClient::from_env()?.leaderboard(id)?;
+            // without access to a leaderboard, the site redirects to an HTML page instead.
+            if !json.trim_start().starts_with('{') {
+                eprintln!(
+                    "Leaderboard {id} is not accessible, check that your session cookie belongs to one of its members."
+                );
+                process::exit(1);
+            }
+            aoc_client::write_file(&path, &json)?;
+            Ok(json)
+        }
+    }
+}
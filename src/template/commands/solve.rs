@@ -1,8 +1,32 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::runner::{InputSource, RunOptions, Solution};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, watch};
+
+pub fn handle(
+    solutions: &[Solution],
+    day: Day,
+    release: bool,
+    dhat: bool,
+    watch: bool,
+    options: &RunOptions,
+) {
+    if watch {
+        if dhat || options.submit.is_some() || options.input == InputSource::Stdin {
+            eprintln!("`--watch` cannot be combined with `--dhat`, `--submit` or `--input -`.");
+            process::exit(1);
+        }
+        watch::watch(day, release, options);
+    }
+
+    // dhat needs its own build profile and allocator, so it always runs the day binary.
+    if let Some(solution) = solutions.iter().find(|s| s.day == day).filter(|_| !dhat) {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+        (solution.run)(options);
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,11 +41,7 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    cmd_args.extend(options.to_args());
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
@@ -0,0 +1,32 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::submissions::Submissions;
+use crate::template::{aoc_client, readme_stars};
+
+/// Rewrite the ⭐️ progress table in the readme from `data/answers.json` and the accepted submissions in `data/submissions.json`.
+pub fn handle() {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("`AOC_YEAR` is not set, set it in `.cargo/config.toml`.");
+        process::exit(1);
+    };
+
+    let answers = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read answers: {e}");
+        process::exit(1);
+    });
+    let submissions = Submissions::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read submissions: {e}");
+        process::exit(1);
+    });
+    let stars = readme_stars::collect(&answers, &submissions, year);
+    let total: usize = stars.iter().map(readme_stars::DayStars::count).sum();
+
+    match readme_stars::update(year, &stars) {
+        Ok(()) => println!("⭐ Updated the readme with {total} star(s)."),
+        Err(e) => {
+            eprintln!("Failed to update the readme: {e:?}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,10 +1,34 @@
 use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, process};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::export::{self, ExportFormat};
+use crate::template::history::{self, History, HistoryEntry};
+use crate::template::protocol::PARSE_PART;
+use crate::template::run_multi::{collect_timings, run_days, summarize_timings};
+use crate::template::runner::{Output, RunOptions, Solution};
+use crate::template::timings::{TimingChange, Timings};
+use crate::template::{
+    ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET, Day, all_days, readme_benchmarks,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Number of most recent runs shown in a sparkline.
+const SPARKLINE_RUNS: usize = 40;
+
+/// Default change in percent beyond which `--compare` flags a part.
+pub const DEFAULT_COMPARE_THRESHOLD: f64 = 10.0;
+
+pub fn handle(
+    solutions: &[Solution],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    variants: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,9 +45,29 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        timed: true,
+        compare_variants: variants,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let results = run_days(solutions, &days_to_run, true, &options, jobs);
+    let timings = summarize_timings(&results);
 
     if store {
+        let (commit, timestamp) = (history::current_commit(), history::now());
+        let entries: Vec<HistoryEntry> = results
+            .iter()
+            .filter_map(|(day, records)| {
+                HistoryEntry::from_records(*day, records, commit.clone(), timestamp)
+            })
+            .collect();
+
+        if let Err(e) = History::append_to_file(&entries) {
+            eprintln!("Failed to append to timing history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -38,3 +82,339 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Export timings, freshly measured for `day` or all days with `run_all` and the stored ones otherwise.
+/// They are written to `out`, the default path of the format if `None`, or stdout for `-`.
+pub fn handle_export(
+    solutions: &[Solution],
+    format: ExportFormat,
+    out: Option<PathBuf>,
+    day: Option<Day>,
+    run_all: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let path = out.unwrap_or_else(|| format.default_path().into());
+    let is_stdout = path.as_os_str() == "-";
+
+    let timings = if day.is_some() || run_all {
+        let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+        let options = RunOptions {
+            timed: true,
+            timeout,
+            ..RunOptions::default()
+        };
+
+        if is_stdout {
+            // keep stdout for the export, the output of the run goes to stderr.
+            let options = RunOptions {
+                output: Output::buffer(),
+                ..options
+            };
+            let results = run_days(solutions, &days_to_run, true, &options, jobs);
+            let _ = io::stderr().write_all(&options.output.take());
+            collect_timings(&results)
+        } else {
+            let results = run_days(solutions, &days_to_run, true, &options, jobs);
+            let timings = summarize_timings(&results);
+            println!();
+            timings
+        }
+    } else {
+        Timings::read_from_file()
+    };
+
+    if timings.data.is_empty() {
+        eprintln!("No timings to export, run `cargo time --store` first or pass a day or `--all`.");
+        process::exit(1);
+    }
+
+    let exported = export::export(&timings, format);
+
+    if is_stdout {
+        print!("{exported}");
+        return;
+    }
+
+    match fs::write(&path, exported) {
+        Ok(()) => println!(
+            "Exported timings of {} day(s) to \"{}\".",
+            timings.data.len(),
+            path.display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// Measure the heap usage of every part of the days that have stored timings, running them under dhat.
+/// With `store`, the usage is added to the stored timings and the readme.
+pub fn handle_memory(
+    solutions: &[Solution],
+    day: Option<Day>,
+    store: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let stored_timings = Timings::read_from_file();
+
+    let days_to_run: HashSet<Day> = stored_timings
+        .data
+        .iter()
+        .map(|t| t.day)
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .collect();
+
+    if days_to_run.is_empty() {
+        eprintln!("No stored timings to add heap usage to, run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let options = RunOptions {
+        memory: true,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let memory: Vec<_> = run_days(solutions, &days_to_run, true, &options, jobs)
+        .into_iter()
+        .flat_map(|(_, records)| records)
+        .filter_map(|r| r.memory.map(|memory| (r.day, r.part, memory)))
+        .collect();
+
+    if store {
+        let (timings, updated) = stored_timings.with_memory(&memory);
+        timings.store_file().unwrap();
+
+        println!();
+        match readme_benchmarks::update(timings) {
+            Ok(()) => println!("Stored heap usage of {updated} part(s)."),
+            Err(_) => eprintln!("Failed to store updated benchmarks."),
+        }
+    }
+}
+
+/// Re-bench days that have stored timings and compare the results with them, without storing anything.
+/// Exits non-zero if any part got slower by more than `threshold` percent.
+pub fn handle_compare(
+    solutions: &[Solution],
+    day: Option<Day>,
+    threshold: f64,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let baseline = Timings::read_from_file();
+
+    let days_to_run: HashSet<Day> = baseline
+        .data
+        .iter()
+        .map(|t| t.day)
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .collect();
+
+    if days_to_run.is_empty() {
+        eprintln!("No stored timings to compare against, run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let options = RunOptions {
+        timed: true,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let results = run_days(solutions, &days_to_run, true, &options, jobs);
+    let timings = summarize_timings(&results);
+    let changes = baseline.compare(&timings);
+
+    warn_about_environment(&baseline, &timings);
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} {ANSI_ITALIC}(threshold ±{threshold}%){ANSI_RESET}"
+    );
+    println!("------------------------------");
+
+    for change in &changes {
+        print_change(change, threshold);
+    }
+
+    let regressions = changes.iter().filter(|c| c.percent() > threshold).count();
+
+    if regressions > 0 {
+        eprintln!(
+            "{ANSI_RED}✖ {regressions} part(s) regressed by more than {threshold}%.{ANSI_RESET}"
+        );
+        process::exit(1);
+    }
+
+    println!("{ANSI_GREEN}✔ No regressions beyond {threshold}%.{ANSI_RESET}");
+}
+
+/// Warn if stored timings were measured on another machine or with another toolchain than the current run.
+fn warn_about_environment(baseline: &Timings, current: &Timings) {
+    let mut unknown = 0;
+
+    for timing in &baseline.data {
+        let Some(current) = current
+            .data
+            .iter()
+            .find(|t| t.day == timing.day)
+            .and_then(|t| t.environment.as_ref())
+        else {
+            continue;
+        };
+
+        let Some(environment) = &timing.environment else {
+            unknown += 1;
+            continue;
+        };
+
+        let differences = environment.differences(current);
+        if !differences.is_empty() {
+            eprintln!(
+                "{ANSI_RED}⚠ Day {} was timed on a different machine or toolchain:{ANSI_RESET} {}",
+                timing.day,
+                differences.join(", ")
+            );
+        }
+    }
+
+    if unknown > 0 {
+        eprintln!(
+            "{ANSI_ITALIC}Note: {unknown} day(s) were stored without machine or toolchain info.{ANSI_RESET}"
+        );
+    }
+}
+
+fn print_change(change: &TimingChange, threshold: f64) {
+    let percent = change.percent();
+    let color = if percent > threshold {
+        ANSI_RED
+    } else if percent < -threshold {
+        ANSI_GREEN
+    } else {
+        ""
+    };
+
+    let part = match change.part {
+        PARSE_PART => "Parse".to_string(),
+        part => format!("Part {part}"),
+    };
+
+    println!(
+        "Day {}  {part:<6}  {:>10} → {:>10}  {color}{percent:>+7.1}%{ANSI_RESET}",
+        change.day,
+        history::format_nanos(change.before_nanos),
+        history::format_nanos(change.after_nanos),
+    );
+}
+
+/// Show how the timings of all days, or every run of a single day, changed over time.
+pub fn handle_history(day: Option<Day>) {
+    let history = History::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read timing history: {e}");
+        process::exit(1);
+    });
+
+    match day {
+        Some(day) => print_day_history(&history.for_day(day), day),
+        None => print_history_overview(&history),
+    }
+}
+
+fn print_history_overview(history: &History) {
+    println!("{ANSI_BOLD}Timing history{ANSI_RESET}");
+    println!("--------------");
+
+    let width = all_days()
+        .map(|day| history.for_day(day).len().min(SPARKLINE_RUNS))
+        .max()
+        .unwrap_or(0);
+
+    if width == 0 {
+        println!("No runs recorded yet, run `cargo time --store` to record one.");
+        return;
+    }
+
+    for day in all_days() {
+        let entries = history.for_day(day);
+        let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+            continue;
+        };
+
+        let totals: Vec<Option<f64>> = recent(&entries)
+            .iter()
+            .map(|e| Some(e.total_nanos()))
+            .collect();
+
+        println!(
+            "Day {day}  {:<width$}  {:>10}  {ANSI_ITALIC}{} over {} run(s){ANSI_RESET}",
+            history::sparkline(&totals),
+            history::format_nanos(last.total_nanos()),
+            format_change(first.total_nanos(), last.total_nanos()),
+            entries.len(),
+        );
+    }
+}
+
+fn print_day_history(entries: &[&HistoryEntry], day: Day) {
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    if entries.is_empty() {
+        println!("No runs recorded yet, run `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    let recent = recent(entries);
+    let columns = [
+        (
+            "Parse",
+            recent.iter().map(|e| e.parse_nanos).collect::<Vec<_>>(),
+        ),
+        ("Part 1", recent.iter().map(|e| e.part_1_nanos).collect()),
+        ("Part 2", recent.iter().map(|e| e.part_2_nanos).collect()),
+    ];
+
+    for (name, values) in columns {
+        if let Some(latest) = values.iter().rev().flatten().next() {
+            println!(
+                "{name:<6}  {}  {}",
+                history::sparkline(&values),
+                history::format_nanos(*latest)
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{:<16}  {:<14}  {:>10}  {:>10}  {:>10}",
+        "Date", "Commit", "Parse", "Part 1", "Part 2"
+    );
+
+    for entry in entries {
+        let cell = |nanos: Option<f64>| nanos.map_or("-".into(), history::format_nanos);
+        println!(
+            "{:<16}  {:<14}  {:>10}  {:>10}  {:>10}",
+            history::format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            cell(entry.parse_nanos),
+            cell(entry.part_1_nanos),
+            cell(entry.part_2_nanos),
+        );
+    }
+}
+
+fn recent<'a>(entries: &[&'a HistoryEntry]) -> Vec<&'a HistoryEntry> {
+    entries[entries.len().saturating_sub(SPARKLINE_RUNS)..].to_vec()
+}
+
+fn format_change(before: f64, after: f64) -> String {
+    if before <= 0.0 {
+        return "±0.0%".into();
+    }
+    format!("{:+.1}%", (after - before) / before * 100.0)
+}
@@ -0,0 +1,71 @@
+use std::io::{Write, stdout};
+use std::process;
+
+use chrono::{Datelike, FixedOffset};
+
+use crate::template::aoc_client::{self, get_year};
+use crate::template::commands::{download, read, scaffold};
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::unlock::{self, Clock, SystemClock};
+use crate::template::{Day, last_day};
+
+/// How often downloading the puzzle is attempted after it unlocked.
+const ATTEMPTS: u32 = 8;
+
+/// Scaffold, download and read the puzzle of the current day.
+/// With `wait`, wait for the next puzzle to unlock first and retry the download until the server has it.
+pub fn handle(wait: bool) {
+    if !wait {
+        let Some(day) = Day::today() else {
+            let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+            let last_day = last_day(SystemClock.now().with_timezone(&offset).year());
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the {}th of december. Please use `scaffold` with a specific day.",
+                last_day.into_inner()
+            );
+            process::exit(1)
+        };
+
+        scaffold::handle(day, false);
+        download::handle(day);
+        read::handle(day);
+        return;
+    }
+
+    let clock = SystemClock;
+    let next = unlock::next_unlock(clock.now());
+
+    if get_year().map(i32::from) != Some(next.year) {
+        eprintln!(
+            "The next puzzle is day {} of {}, set `AOC_YEAR` to {} in `.cargo/config.toml` first.",
+            next.day, next.year, next.year
+        );
+        process::exit(1);
+    }
+
+    println!(
+        "🎄 Waiting for day {} of {} to unlock.",
+        next.day, next.year
+    );
+    unlock::wait_until(&clock, next.at, |remaining| {
+        print!("\r⏳ {} ", unlock::format_countdown(remaining));
+        let _ = stdout().flush();
+    });
+    println!("\r🔓 Day {} is unlocked!", next.day);
+
+    scaffold::handle(next.day, false);
+
+    let result = unlock::retry(
+        &clock,
+        ATTEMPTS,
+        aoc_client::AocClientError::is_retriable,
+        || aoc_client::download(next.day),
+    )
+    .and_then(|()| aoc_client::read(next.day));
+
+    if let Err(e) = result {
+        eprintln!("failed to download day {}: {e}", next.day);
+        process::exit(1);
+    }
+}
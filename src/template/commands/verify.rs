@@ -0,0 +1,199 @@
+use std::collections::BTreeSet;
+use std::process;
+use std::time::Duration;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::protocol::{PARSE_PART, PartRecord};
+use crate::template::run_multi::run_days;
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, Day, all_days};
+
+/// Run every day and compare the results with `data/answers.json`.
+/// With `accept`, results for parts without a known answer are recorded as accepted.
+pub fn handle(solutions: &[Solution], accept: bool, timeout: Option<Duration>, jobs: usize) {
+    let mut answers = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read answers: {e}");
+        process::exit(1);
+    });
+    let results = run_days(
+        solutions,
+        &all_days().collect(),
+        true,
+        &RunOptions {
+            timeout,
+            ..RunOptions::default()
+        },
+        jobs,
+    );
+
+    let mut mismatches = 0;
+    let mut accepted = 0;
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    let records: Vec<PartRecord> = results
+        .into_iter()
+        .flat_map(|(_, records)| records)
+        .collect();
+
+    for PartCheck {
+        day,
+        part,
+        verdict,
+        record,
+    } in check(&answers, &records)
+    {
+        match verdict {
+            Verdict::Correct => {
+                println!("Day {day} Part {part}: {ANSI_GREEN}✔{ANSI_RESET}");
+            }
+            Verdict::Mismatch { expected } => {
+                mismatches += 1;
+                let actual = match record.map(|r| (&r.answer, &r.error)) {
+                    Some((Some(answer), _)) => format!("got {answer}"),
+                    Some((None, Some(error))) => error.clone(),
+                    Some((None, None)) | None => "no result".into(),
+                };
+                println!(
+                    "Day {day} Part {part}: {ANSI_RED}✖ {actual}, expected {expected}{ANSI_RESET}"
+                );
+            }
+            Verdict::Unknown => match record.and_then(|r| r.answer.as_ref()) {
+                Some(answer) if accept => {
+                    accepted += 1;
+                    answers.set(day, part, answer);
+                    println!("Day {day} Part {part}: accepted {answer}");
+                }
+                _ => println!("Day {day} Part {part}: no known answer"),
+            },
+        }
+    }
+
+    if accepted > 0 {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answers: {e}");
+            process::exit(1);
+        }
+        println!("Stored {accepted} new answer(s).");
+    }
+
+    if mismatches > 0 {
+        eprintln!("{ANSI_RED}{mismatches} answer(s) do not match.{ANSI_RESET}");
+        process::exit(1);
+    }
+}
+
+/// The result of a part compared with its known answer.
+#[derive(Debug, PartialEq)]
+struct PartCheck<'a> {
+    day: Day,
+    part: u8,
+    verdict: Verdict,
+    record: Option<&'a PartRecord>,
+}
+
+/// Compare the records of a run with the known answers, in order of day and part.
+/// Every known answer is checked, so a part without a record, e.g. because its binary did not build, is a mismatch.
+fn check<'a>(answers: &Answers, records: &'a [PartRecord]) -> Vec<PartCheck<'a>> {
+    let known = answers.data.iter().flat_map(|answer| {
+        [(1, &answer.part_1), (2, &answer.part_2)]
+            .into_iter()
+            .filter(|(_, value)| value.is_some())
+            .map(|(part, _)| (answer.day, part))
+    });
+    let recorded = records
+        .iter()
+        .filter(|r| r.part != PARSE_PART)
+        .map(|r| (r.day, r.part));
+
+    known
+        .chain(recorded)
+        .collect::<BTreeSet<(Day, u8)>>()
+        .into_iter()
+        .map(|(day, part)| {
+            let record = records.iter().find(|r| r.day == day && r.part == part);
+            PartCheck {
+                day,
+                part,
+                verdict: answers.verify(day, part, record.and_then(|r| r.answer.as_deref())),
+                record,
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartCheck, check};
+    use crate::day;
+    use crate::template::answers::{Answers, Verdict};
+    use crate::template::fixtures::record;
+    use crate::template::protocol::{PartRecord, Status};
+
+    #[test]
+    fn checks_records_against_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 2, "43");
+
+        let records = [
+            record(0, 10.0, Status::Solved),
+            record(1, 10.0, Status::Solved),
+            record(2, 10.0, Status::Solved),
+        ];
+        let verdicts: Vec<Verdict> = check(&answers, &records)
+            .into_iter()
+            .map(|c| c.verdict)
+            .collect();
+
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Correct,
+                Verdict::Mismatch {
+                    expected: "43".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_parts_without_records() {
+        let mut answers = Answers::default();
+        answers.set(day!(8), 1, "42");
+
+        assert_eq!(
+            check(&answers, &[]),
+            vec![PartCheck {
+                day: day!(8),
+                part: 1,
+                verdict: Verdict::Mismatch {
+                    expected: "42".into()
+                },
+                record: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn lists_results_without_known_answers() {
+        let records = [PartRecord {
+            day: day!(2),
+            ..record(1, 10.0, Status::Solved)
+        }];
+
+        assert_eq!(
+            check(&Answers::default(), &records),
+            vec![PartCheck {
+                day: day!(2),
+                part: 1,
+                verdict: Verdict::Unknown,
+                record: Some(&records[0]),
+            }]
+        );
+    }
+}
@@ -5,8 +5,8 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+/// Offset of the Advent of Code server's timezone (EST), puzzles unlock at midnight in it.
+pub(crate) const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -45,11 +45,11 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st of december and the last day of the event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(last_day(today.year()).0) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -100,6 +100,11 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// The last day of the event of `year`. Since 2025, the event has 12 days instead of 25.
+pub fn last_day(year: i32) -> Day {
+    if year >= 2025 { Day(12) } else { Day(25) }
+}
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -154,7 +159,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{Day, all_days, last_day};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +192,12 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn shortens_events_from_2025() {
+        assert_eq!(last_day(2024), Day(25));
+        assert_eq!(last_day(2025), Day(12));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
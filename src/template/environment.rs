@@ -0,0 +1,247 @@
+/// The machine and toolchain that timings were measured with.
+/// The toolchain is captured by `build.rs` for every binary, so each day binary reports its own build in its result records.
+use std::{
+    collections::HashMap, env, fs, num::NonZeroUsize, process::Command, sync::OnceLock, thread,
+};
+use tinyjson::JsonValue;
+
+/// Set to `1` or `true` to record the hostname along with timings.
+const RECORD_HOSTNAME_ENV: &str = "AOC_RECORD_HOSTNAME";
+
+static CURRENT: OnceLock<Environment> = OnceLock::new();
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    /// CPU model name, as reported by `/proc/cpuinfo`.
+    pub cpu: Option<String>,
+    pub cores: usize,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    pub profile: String,
+    /// Enabled cargo features, e.g. `cbc`.
+    pub features: Vec<String>,
+    pub hostname: Option<String>,
+}
+
+impl Environment {
+    /// The environment of the running binary, read once per process.
+    pub fn current() -> Self {
+        CURRENT.get_or_init(Environment::detect).clone()
+    }
+
+    fn detect() -> Self {
+        Environment {
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| cpu_model(&cpuinfo)),
+            cores: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            rustc: option_env!("AOC_RUSTC_VERSION").map(Into::into),
+            profile: option_env!("AOC_PROFILE").unwrap_or("unknown").into(),
+            features: option_env!("AOC_FEATURES")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(Into::into)
+                .collect(),
+            hostname: matches!(env::var(RECORD_HOSTNAME_ENV).as_deref(), Ok("1" | "true"))
+                .then(hostname)
+                .flatten(),
+        }
+    }
+
+    /// The fields that differ between `self` and `other`, formatted as `field: self → other`.
+    /// The hostname is opt-in, so it is only compared if both environments recorded it.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let describe = |value: &Option<String>| value.as_deref().unwrap_or("unknown").to_string();
+        let hostnames = match (&self.hostname, &other.hostname) {
+            (Some(before), Some(after)) => (before.clone(), after.clone()),
+            _ => (String::new(), String::new()),
+        };
+
+        [
+            ("cpu", describe(&self.cpu), describe(&other.cpu)),
+            ("cores", self.cores.to_string(), other.cores.to_string()),
+            ("rustc", describe(&self.rustc), describe(&other.rustc)),
+            ("profile", self.profile.clone(), other.profile.clone()),
+            (
+                "features",
+                format!("[{}]", self.features.join(", ")),
+                format!("[{}]", other.features.join(", ")),
+            ),
+            ("hostname", hostnames.0, hostnames.1),
+        ]
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(field, before, after)| format!("{field}: {before} → {after}"))
+        .collect()
+    }
+}
+
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_string())
+    })
+}
+
+fn hostname() -> Option<String> {
+    let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| {
+            let output = Command::new("hostname").output().ok()?;
+            String::from_utf8(output.stdout).ok()
+        })?;
+
+    let hostname = hostname.trim();
+    (!hostname.is_empty()).then(|| hostname.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let optional = |value: &Option<String>| match value {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), optional(&value.cpu));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), optional(&value.rustc));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .map(|f| JsonValue::String(f.clone()))
+                    .collect(),
+            ),
+        );
+        map.insert("hostname".into(), optional(&value.hostname));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let optional = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected environment.{key} to be null or string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected environment.profile to be a string.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected environment.features to be an array.")?
+            .iter()
+            .map(|f| {
+                f.get::<String>()
+                    .cloned()
+                    .ok_or("Expected environment.features to contain strings.")
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Environment {
+            cpu: optional("cpu")?.cloned(),
+            cores: cores as usize,
+            rustc: optional("rustc")?.cloned(),
+            profile: profile.clone(),
+            features,
+            hostname: optional("hostname")?.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Environment, cpu_model};
+    use tinyjson::JsonValue;
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: Some("AMD Ryzen 7 5800X 8-Core Processor".into()),
+            cores: 16,
+            rustc: Some("rustc 1.85.0 (4d91de4e4 2025-02-17)".into()),
+            profile: "release".into(),
+            features: vec!["cbc".into()],
+            hostname: None,
+        }
+    }
+
+    #[test]
+    fn reads_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\nflags\t\t: fpu\n";
+        assert_eq!(
+            cpu_model(cpuinfo),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+        assert_eq!(cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn round_trips_environments() {
+        let environment = get_mock_environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json).unwrap(), environment);
+    }
+
+    #[test]
+    fn lists_differences() {
+        let before = get_mock_environment();
+        let after = Environment {
+            cores: 8,
+            features: vec![],
+            ..get_mock_environment()
+        };
+
+        assert!(before.differences(&before).is_empty());
+        assert_eq!(
+            before.differences(&after),
+            vec!["cores: 16 → 8", "features: [cbc] → []"]
+        );
+    }
+
+    #[test]
+    fn compares_hostnames_only_if_both_are_recorded() {
+        let before = Environment {
+            hostname: Some("desktop".into()),
+            ..get_mock_environment()
+        };
+        let after = Environment {
+            hostname: Some("laptop".into()),
+            ..get_mock_environment()
+        };
+
+        assert!(before.differences(&get_mock_environment()).is_empty());
+        assert_eq!(
+            before.differences(&after),
+            vec!["hostname: desktop → laptop"]
+        );
+    }
+}
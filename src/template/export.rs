@@ -0,0 +1,226 @@
+/// Exports timings to formats that spreadsheets and dashboards understand.
+/// Every measured part of every day becomes one row.
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::protocol::PARSE_PART;
+use crate::template::timings::{PartTiming, Timings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    JsonLines,
+    /// The `customSmallerIsBetter` format of github-action-benchmark.
+    Gab,
+}
+
+impl ExportFormat {
+    /// Where an export is written to if no other path is given.
+    pub fn default_path(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "./data/timings.csv",
+            ExportFormat::JsonLines => "./data/timings.jsonl",
+            ExportFormat::Gab => "./data/timings.gab.json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" => Ok(ExportFormat::JsonLines),
+            "gab" => Ok(ExportFormat::Gab),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected one of: csv, jsonl, gab."
+            )),
+        }
+    }
+}
+
+pub fn export(timings: &Timings, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::JsonLines => to_json_lines(timings),
+        ExportFormat::Gab => to_gab(timings),
+    }
+}
+
+fn rows(timings: &Timings) -> impl Iterator<Item = (Day, u8, PartTiming)> + '_ {
+    timings.data.iter().flat_map(|timing| {
+        [
+            (PARSE_PART, timing.parse),
+            (1, timing.part_1),
+            (2, timing.part_2),
+        ]
+        .into_iter()
+        .filter_map(move |(part, t)| t.map(|t| (timing.day, part, t)))
+    })
+}
+
+fn part_label(part: u8) -> &'static str {
+    match part {
+        PARSE_PART => "parse",
+        1 => "part_1",
+        _ => "part_2",
+    }
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec!["day,part,median_nanos,std_dev_nanos,samples".to_string()];
+
+    for (day, part, timing) in rows(timings) {
+        lines.push(format!(
+            "{day},{},{},{},{}",
+            part_label(part),
+            timing.median_nanos,
+            timing
+                .std_dev_nanos
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+            timing.samples
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn to_json_lines(timings: &Timings) -> String {
+    rows(timings)
+        .map(|(day, part, timing)| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(day.to_string()));
+            map.insert("part".into(), JsonValue::String(part_label(part).into()));
+            map.insert(
+                "median_nanos".into(),
+                JsonValue::Number(timing.median_nanos),
+            );
+            map.insert(
+                "std_dev_nanos".into(),
+                timing
+                    .std_dev_nanos
+                    .map_or(JsonValue::Null, JsonValue::Number),
+            );
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("samples".into(), JsonValue::Number(timing.samples as f64));
+
+            format!("{}\n", JsonValue::Object(map).stringify().unwrap())
+        })
+        .collect()
+}
+
+fn to_gab(timings: &Timings) -> String {
+    let entries = rows(timings)
+        .map(|(day, part, timing)| {
+            let name = match part {
+                PARSE_PART => format!("Day {day} - Parse"),
+                part => format!("Day {day} - Part {part}"),
+            };
+
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("name".into(), JsonValue::String(name));
+            map.insert("unit".into(), JsonValue::String("ns".into()));
+            map.insert("value".into(), JsonValue::Number(timing.median_nanos));
+            if let Some(std_dev) = timing.std_dev_nanos {
+                map.insert("range".into(), JsonValue::String(format!("± {std_dev}")));
+            }
+            // NOTE: timings migrated from version 1 of `timings.json` have an unknown sample count.
+            if timing.samples > 0 {
+                map.insert(
+                    "extra".into(),
+                    JsonValue::String(format!("{} samples", timing.samples)),
+                );
+            }
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    format!("{}\n", JsonValue::Array(entries).format().unwrap())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExportFormat, export};
+    use crate::template::fixtures::get_mock_timings;
+    use crate::template::timings::{PartTiming, Timings};
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    /// The shared mock timings, with a parse step measured over several samples.
+    fn get_benched_timings() -> Timings {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some(PartTiming {
+            median_nanos: 250.0,
+            std_dev_nanos: Some(12.0),
+            samples: 100,
+            memory: None,
+        });
+        timings
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::from_str("csv"), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_str("jsonl"), Ok(ExportFormat::JsonLines));
+        assert_eq!(ExportFormat::from_str("gab"), Ok(ExportFormat::Gab));
+        assert!(ExportFormat::from_str("xlsx").is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            export(&get_benched_timings(), ExportFormat::Csv),
+            [
+                "day,part,median_nanos,std_dev_nanos,samples",
+                "01,parse,250,12,100",
+                "01,part_1,10000000,,1",
+                "01,part_2,20000000,,1",
+                "02,part_1,30000000,,1",
+                "02,part_2,40000000,,1",
+                "04,part_1,40000000,,1",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let exported = export(&get_benched_timings(), ExportFormat::JsonLines);
+        let rows: Vec<JsonValue> = exported.lines().map(|l| l.parse().unwrap()).collect();
+        assert_eq!(rows.len(), 6);
+
+        let last: &HashMap<String, JsonValue> = rows[5].get().unwrap();
+        assert_eq!(last["day"], JsonValue::String("04".into()));
+        assert_eq!(last["part"], JsonValue::String("part_1".into()));
+        assert_eq!(last["median_nanos"], JsonValue::Number(4e7));
+        assert_eq!(last["std_dev_nanos"], JsonValue::Null);
+        assert_eq!(last["samples"], JsonValue::Number(1.0));
+    }
+
+    #[test]
+    fn exports_github_action_benchmark() {
+        let exported = export(&get_benched_timings(), ExportFormat::Gab);
+        let json: JsonValue = exported.parse().unwrap();
+        let entries: &Vec<JsonValue> = json.get().unwrap();
+        assert_eq!(entries.len(), 6);
+
+        let first: &HashMap<String, JsonValue> = entries[0].get().unwrap();
+        assert_eq!(first["name"], JsonValue::String("Day 01 - Parse".into()));
+        assert_eq!(first["unit"], JsonValue::String("ns".into()));
+        assert_eq!(first["value"], JsonValue::Number(250.0));
+        assert_eq!(first["range"], JsonValue::String("± 12".into()));
+        assert_eq!(first["extra"], JsonValue::String("100 samples".into()));
+
+        let last: &HashMap<String, JsonValue> = entries[5].get().unwrap();
+        assert_eq!(last["name"], JsonValue::String("Day 04 - Part 1".into()));
+        assert!(!last.contains_key("range"));
+    }
+}
@@ -0,0 +1,66 @@
+/// Records and timings shared by the tests of the template modules.
+use crate::day;
+use crate::template::protocol::{PartRecord, Status};
+use crate::template::timings::{PartTiming, Timing, Timings};
+
+/// A record of day 1 measured over 10 samples. Solved parts have the answer `42`.
+pub fn record(part: u8, median_nanos: f64, status: Status) -> PartRecord {
+    PartRecord {
+        day: day!(1),
+        part,
+        answer: (status == Status::Solved).then(|| "42".into()),
+        median_nanos,
+        std_dev_nanos: 1000.0,
+        samples: 10,
+        status,
+        error: None,
+        memory: None,
+        environment: None,
+    }
+}
+
+/// A part timing measured once.
+pub fn timing(median_nanos: f64) -> Option<PartTiming> {
+    Some(PartTiming {
+        median_nanos,
+        std_dev_nanos: None,
+        samples: 1,
+        memory: None,
+    })
+}
+
+pub fn millis(millis: f64) -> Option<PartTiming> {
+    timing(millis * 1e6)
+}
+
+/// Timings of days 1, 2 and 4, where part 2 of day 4 is not solved.
+pub fn get_mock_timings() -> Timings {
+    Timings {
+        data: vec![
+            Timing {
+                day: day!(1),
+                parse: None,
+                part_1: millis(10.0),
+                part_2: millis(20.0),
+                total_nanos: 3e+10,
+                environment: None,
+            },
+            Timing {
+                day: day!(2),
+                parse: None,
+                part_1: millis(30.0),
+                part_2: millis(40.0),
+                total_nanos: 7e+10,
+                environment: None,
+            },
+            Timing {
+                day: day!(4),
+                parse: None,
+                part_1: millis(40.0),
+                part_2: None,
+                total_nanos: 4e+10,
+                environment: None,
+            },
+        ],
+    }
+}
@@ -0,0 +1,344 @@
+/// History of timed runs, appended to by `cargo time --store` and shown by `cargo time --history`.
+/// Every line of the history file is one JSON object describing a single day of a single run.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::protocol::{PARSE_PART, PartRecord, Status};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// The median timings of one day in one run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub day: Day,
+    /// Abbreviated hash of the commit that was checked out, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub parse_nanos: Option<f64>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+}
+
+impl HistoryEntry {
+    /// Build an entry from the records of a day, `None` if no part was solved.
+    pub fn from_records(
+        day: Day,
+        records: &[PartRecord],
+        commit: Option<String>,
+        timestamp: u64,
+    ) -> Option<Self> {
+        let nanos = |part: u8| {
+            records
+                .iter()
+                .find(|r| r.part == part && r.status == Status::Solved)
+                .map(|r| r.median_nanos)
+        };
+
+        let entry = HistoryEntry {
+            day,
+            commit,
+            timestamp,
+            parse_nanos: nanos(PARSE_PART),
+            part_1_nanos: nanos(1),
+            part_2_nanos: nanos(2),
+        };
+
+        (entry.part_1_nanos.is_some() || entry.part_2_nanos.is_some()).then_some(entry)
+    }
+
+    pub fn total_nanos(&self) -> f64 {
+        [self.parse_nanos, self.part_1_nanos, self.part_2_nanos]
+            .iter()
+            .flatten()
+            .sum()
+    }
+}
+
+/// All recorded runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Read the history file. If not present, returns an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(HISTORY_FILE_PATH) {
+            Ok(contents) => History::try_from(contents.as_str()),
+            Err(_) => Ok(History::default()),
+        }
+    }
+
+    /// Append entries to the history file, creating it if needed.
+    pub fn append_to_file(entries: &[HistoryEntry]) -> Result<(), io::Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        for entry in entries {
+            let line = JsonValue::from(entry)
+                .stringify()
+                .map_err(|e| io::Error::other(e.to_string()))?;
+            writeln!(file, "{line}")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn for_day(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.entries.iter().filter(|e| e.day == day).collect()
+    }
+}
+
+/// The commit that is currently checked out, if this is a git repository.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_timestamp(timestamp: u64) -> String {
+    // days to civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let z = (timestamp / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let secs_of_day = timestamp % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/// Renders values as a sparkline, scaled between their minimum and maximum. Missing values are left blank.
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let present = values.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max <= min => BARS[BARS.len() / 2],
+            Some(x) => {
+                let level = (x - min) / (max - min) * (BARS.len() - 1) as f64;
+                BARS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for History {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let entries = value
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let json = JsonValue::from_str(l).or(Err("History line is not valid JSON."))?;
+                HistoryEntry::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { entries })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        for (key, nanos) in [
+            ("parse_nanos", value.parse_nanos),
+            ("part_1_nanos", value.part_1_nanos),
+            ("part_2_nanos", value.part_2_nanos),
+        ] {
+            map.insert(
+                key.into(),
+                match nanos {
+                    Some(x) => JsonValue::Number(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let nanos = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    if v.is_null() {
+                        None
+                    } else {
+                        v.get::<f64>().copied()
+                    }
+                })
+                .ok_or(format!("Expected entry.{key} to be null or number."))
+        };
+
+        Ok(HistoryEntry {
+            day,
+            commit: commit.cloned(),
+            timestamp: timestamp as u64,
+            parse_nanos: nanos("parse_nanos")?,
+            part_1_nanos: nanos("part_1_nanos")?,
+            part_2_nanos: nanos("part_2_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{History, HistoryEntry, format_timestamp, sparkline};
+    use crate::day;
+    use crate::template::fixtures::record;
+    use crate::template::protocol::Status;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn builds_entries_from_records() {
+        let records = [
+            record(1, 1500.0, Status::Solved),
+            record(2, 10.0, Status::Unsolved),
+        ];
+        let entry =
+            HistoryEntry::from_records(day!(1), &records, Some("abc1234".into()), 1000).unwrap();
+
+        assert_eq!(entry.parse_nanos, None);
+        assert_eq!(entry.part_1_nanos, Some(1500.0));
+        assert_eq!(entry.part_2_nanos, None);
+        assert!(HistoryEntry::from_records(day!(1), &records[1..], None, 1000).is_none());
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let entry = HistoryEntry {
+            day: day!(12),
+            commit: None,
+            timestamp: 1_733_011_200,
+            parse_nanos: Some(200.0),
+            part_1_nanos: Some(1500.0),
+            part_2_nanos: None,
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let history = History::try_from(format!("{line}\n\n{line}\n").as_str()).unwrap();
+        assert_eq!(history.entries, vec![entry.clone(), entry]);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(
+            format_timestamp(1_733_011_200 + 3_600 * 5 + 60 * 7),
+            "2024-12-01 05:07"
+        );
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(sparkline(&[Some(1.0), Some(8.0), None, Some(4.5)]), "▁█ ▅");
+        assert_eq!(sparkline(&[Some(3.0), Some(3.0)]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
@@ -0,0 +1,357 @@
+/// Private leaderboards of adventofcode.com, shown by `cargo leaderboard`.
+/// The site asks not to fetch a leaderboard more than once every 15 minutes, so fetched leaderboards are cached in `data/leaderboards`.
+use std::{collections::HashMap, fs, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+/// How long a cached leaderboard is shown before it is fetched again.
+pub const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous members.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Unix timestamps at which the stars of a day were collected, by part.
+    pub completions: HashMap<Day, [Option<u64>; 2]>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn last_star(&self) -> u64 {
+        self.completions
+            .values()
+            .flatten()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(u64::MAX)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Members ordered like on the website: by local score, ties go to whoever got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star(), m.id));
+        members
+    }
+
+    /// The latest day that any member collected a star for.
+    pub fn latest_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .flat_map(|m| m.completions.keys())
+            .max()
+            .copied()
+    }
+}
+
+/// Path of the cached JSON of a leaderboard.
+pub fn cache_path(id: u64) -> String {
+    format!("data/leaderboards/{id}.json")
+}
+
+/// How long ago a leaderboard was cached, `None` if it was never fetched.
+pub fn cache_age(path: &str) -> Option<Duration> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.elapsed().unwrap_or_default())
+}
+
+/// Unix timestamp at which the puzzle of a day unlocks.
+fn unlock_timestamp(year: u16, day: Day) -> u64 {
+    // days from the epoch to the 1st of december, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = u64::from(year);
+    let era = year / 400;
+    let yoe = year % 400;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + 275;
+    let days = era * 146_097 + doe - 719_468 + u64::from(day.into_inner()) - 1;
+
+    #[allow(clippy::cast_sign_loss)]
+    let offset = (-SERVER_UTC_OFFSET * 3600) as u64;
+    days * 86_400 + offset
+}
+
+/// Formats the time it took to solve a part as `HH:MM:SS`, or `>24h` like the website does.
+fn format_completion(secs: u64) -> String {
+    if secs >= 86_400 {
+        return ">24h".into();
+    }
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Renders the standings with the stars of every day, followed by the completion times of `day`.
+pub fn render(leaderboard: &Leaderboard, day: Option<Day>) -> String {
+    let mut out = format!(
+        "{ANSI_BOLD}Private leaderboard {}{ANSI_RESET}\n",
+        leaderboard.year
+    );
+
+    let Some(latest) = leaderboard.latest_day() else {
+        out.push_str("No stars collected yet.\n");
+        return out;
+    };
+
+    let days: Vec<Day> = (1..=latest.into_inner()).filter_map(Day::new).collect();
+    let ones: String = days.iter().map(|d| d.to_string().remove(1)).collect();
+    out.push('\n');
+    if latest.into_inner() >= 10 {
+        let tens: String = days.iter().map(|d| d.to_string().remove(0)).collect();
+        out.push_str(&format!("{:>18}{}\n", "", tens.replace('0', " ")));
+    }
+    out.push_str(&format!(
+        "{:>4} {:>5} {:>5}  {ones}  Name\n",
+        "", "Score", "Stars"
+    ));
+
+    for (rank, member) in (1..).zip(leaderboard.ranked()) {
+        let stars: String = days
+            .iter()
+            .map(|d| match member.completions.get(d) {
+                Some([Some(_), Some(_)]) => '★',
+                Some([Some(_), None]) => '☆',
+                _ => '·',
+            })
+            .collect();
+
+        out.push_str(&format!(
+            "{:>4} {:>5} {:>5}  {stars}  {}\n",
+            format!("{rank})"),
+            member.local_score,
+            member.stars,
+            member.display_name()
+        ));
+    }
+
+    let day = day.unwrap_or(latest);
+    let unlock = unlock_timestamp(leaderboard.year, day);
+    let mut solvers: Vec<(&Member, [Option<u64>; 2])> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| m.completions.get(&day).map(|parts| (m, *parts)))
+        .collect();
+    solvers.sort_by_key(|(m, [one, two])| (two.is_none(), *two, *one, m.id));
+
+    out.push_str(&format!(
+        "\n{ANSI_BOLD}Day {day}{ANSI_RESET}  {ANSI_ITALIC}time since the puzzle unlocked{ANSI_RESET}\n"
+    ));
+
+    if solvers.is_empty() {
+        out.push_str("No stars collected yet.\n");
+        return out;
+    }
+
+    out.push_str(&format!("{:>8}  {:>8}  Name\n", "Part 1", "Part 2"));
+    for (member, parts) in solvers {
+        let [one, two] = parts.map(|ts| {
+            ts.map_or("-".into(), |ts| {
+                format_completion(ts.saturating_sub(unlock))
+            })
+        });
+        out.push_str(&format!("{one:>8}  {two:>8}  {}\n", member.display_name()));
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|event| event.parse().ok())
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?;
+
+        Ok(Leaderboard {
+            year,
+            members: members
+                .values()
+                .map(Member::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected member.{key} to be a number."))
+        };
+
+        let levels = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        let mut completions = HashMap::new();
+        for (day, parts) in levels {
+            let day = Day::from_str(day).map_err(|e| e.to_string())?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected the completions of a day to be an object.")?;
+
+            let star = |part: &str| {
+                parts
+                    .get(part)
+                    .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                    .and_then(|v| v.get("get_star_ts"))
+                    .and_then(|v| v.get::<f64>())
+                    .map(|ts| *ts as u64)
+            };
+            completions.insert(day, [star("1"), star("2")]);
+        }
+
+        Ok(Member {
+            id: number("id")? as u64,
+            name: json.get("name").and_then(|v| v.get::<String>()).cloned(),
+            stars: number("stars")? as u32,
+            local_score: number("local_score")? as u32,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Leaderboard, format_completion, render, unlock_timestamp};
+    use crate::day;
+
+    /// A trimmed down response of `/2024/leaderboard/private/view/<id>.json`.
+    const FIXTURE: &str = r#"{
+        "owner_id": 1,
+        "event": "2024",
+        "day1_ts": 1733029200,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 11, "global_score": 0, "last_star_ts": 1733117400,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029500, "star_index": 1 },
+                        "2": { "get_star_ts": 1733030100, "star_index": 3 }
+                    },
+                    "2": { "1": { "get_star_ts": 1733117400, "star_index": 5 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": "Bob", "stars": 4, "local_score": 11, "global_score": 0, "last_star_ts": 1733209200,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029800, "star_index": 2 },
+                        "2": { "get_star_ts": 1733119200, "star_index": 6 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1733116000, "star_index": 4 },
+                        "2": { "get_star_ts": 1733209200, "star_index": 7 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": null, "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn fixture() -> Leaderboard {
+        Leaderboard::try_from(FIXTURE.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = fixture();
+        assert_eq!(leaderboard.year, 2024);
+        assert_eq!(leaderboard.latest_day(), Some(day!(2)));
+
+        let names: Vec<String> = leaderboard
+            .ranked()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        // Alice and Bob are tied, but Alice got her last star first.
+        assert_eq!(names, ["Alice", "Bob", "(anonymous user #3)"]);
+
+        let alice = leaderboard.ranked()[0];
+        assert_eq!(alice.completions[&day!(2)], [Some(1_733_117_400), None]);
+    }
+
+    #[test]
+    fn times_completions_from_the_unlock() {
+        // 2024-12-01 05:00 UTC.
+        assert_eq!(unlock_timestamp(2024, day!(1)), 1_733_029_200);
+        assert_eq!(
+            unlock_timestamp(2024, day!(25)),
+            1_733_029_200 + 24 * 86_400
+        );
+        assert_eq!(format_completion(3_725), "01:02:05");
+        assert_eq!(format_completion(86_400), ">24h");
+    }
+
+    #[test]
+    fn renders_tables() {
+        let table = render(&fixture(), None);
+        let lines: Vec<&str> = table.lines().skip(2).collect();
+
+        assert_eq!(
+            lines,
+            [
+                "     Score Stars  12  Name",
+                "  1)    11     3  ★☆  Alice",
+                "  2)    11     4  ★★  Bob",
+                "  3)     0     0  ··  (anonymous user #3)",
+                "",
+                "\u{1b}[1mDay 02\u{1b}[0m  \u{1b}[3mtime since the puzzle unlocked\u{1b}[0m",
+                "  Part 1    Part 2  Name",
+                "00:06:40      >24h  Bob",
+                "00:30:00         -  Alice",
+            ]
+        );
+
+        let table = render(&fixture(), Some(day!(1)));
+        assert!(table.ends_with("00:05:00  00:15:00  Alice\n00:10:00      >24h  Bob\n"));
+
+        let table = render(&fixture(), Some(day!(3)));
+        assert!(table.ends_with("No stars collected yet.\n"));
+    }
+}
@@ -1,19 +1,37 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod export;
+pub mod protocol;
 pub mod runner;
 
 pub use day::*;
 
+mod benchmark_chart;
 mod day;
+mod environment;
+#[cfg(feature = "test_lib")]
+mod fixtures;
+mod history;
+mod leaderboard;
+mod puzzle_examples;
+mod puzzle_markdown;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod submissions;
 mod timings;
+#[cfg(feature = "today")]
+mod unlock;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
@@ -36,33 +54,157 @@ pub fn read_file_part<T: std::fmt::Display>(folder: &str, day: Day, part: T) ->
     f.expect("could not open input file")
 }
 
+/// Fails the current test if the closure makes more than `max` heap allocations, and returns its result otherwise.
+///
+/// ```ignore
+/// let input = parse(&advent_of_code::template::read_file("examples", DAY));
+/// advent_of_code::assert_allocations!(max = 2, || simulate_beams(input));
+/// ```
+///
+/// Allocations are counted with dhat, so this only checks anything under `cargo test --features dhat-heap`.
+/// Without the feature, the closure is just called.
+/// dhat counts the allocations of all threads, so run these tests with `-- --test-threads=1` to keep other tests out of the count.
+#[macro_export]
+macro_rules! assert_allocations {
+    (max = $max:expr, $func:expr $(,)?) => {{
+        let (result, allocations) = $crate::template::runner::count_allocations($func);
+        if let Some(allocations) = allocations {
+            let max: u64 = $max;
+            assert!(
+                allocations <= max,
+                "expected at most {max} heap allocations, but {allocations} were made"
+            );
+        }
+        result
+    }};
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse: <fn>`, the input is parsed once by `<fn>(&str)` and the parts receive a reference to the parsed value.
+/// The parse step is timed separately from the parts.
+///
+/// With `variants: { part_two: [name => <fn>, ...] }`, alternative implementations of a part can be registered.
+/// They can be selected with `--variant <name>` and compared with `--variants`.
+///
+/// Parts run on a worker thread, so a panic or a run past `--timeout <seconds>` fails only that part.
+/// For this, the input (and the parsed value) is leaked to live for the rest of the program,
+/// which also means that a parsed value has to be `Send + Sync`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1, []] [part_two, 2, []]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1, []]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2, []]);
+    };
+    ($day:expr, parse: $parse:expr) => {
+        $crate::solution!(@impl $day, parse: $parse, [part_one, 1, []] [part_two, 2, []]);
+    };
+    ($day:expr, 1, parse: $parse:expr) => {
+        $crate::solution!(@impl $day, parse: $parse, [part_one, 1, []]);
+    };
+    ($day:expr, 2, parse: $parse:expr) => {
+        $crate::solution!(@impl $day, parse: $parse, [part_two, 2, []]);
+    };
+    (
+        $day:expr, $(parse: $parse:expr,)? variants: {
+            $(part_one: [$($name_1:ident => $func_1:expr),+ $(,)?] $(,)?)?
+            $(part_two: [$($name_2:ident => $func_2:expr),+ $(,)?] $(,)?)?
+        }
+    ) => {
+        $crate::solution!(
+            @impl $day, $(parse: $parse,)?
+            [part_one, 1, [$($($name_1 => $func_1),+)?]]
+            [part_two, 2, [$($($name_2 => $func_2),+)?]]
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:expr, [$($name:ident => $variant:expr),*]] )*) => {
+        $crate::solution!(@common $day);
+
+        /// Runs all parts against the selected input. Called by `main` and the in-process solution registry.
+        pub fn run(
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::protocol::PartRecord> {
+            let input = $crate::template::runner::read_input(DAY, options);
+            let mut records = vec![];
+            $(
+                records.push($crate::solution!(
+                    @part $func, $part, [$($name => $variant),*], input, options
+                ));
+            )*
+            $crate::template::runner::submit_records(&records, options, run);
+            records
+        }
+    };
+
+    (@impl $day:expr, parse: $parse:expr, $( [$func:expr, $part:expr, [$($name:ident => $variant:expr),*]] )*) => {
+        $crate::solution!(@common $day);
+
+        /// Parses the selected input once and runs all parts against it. Called by `main` and the in-process solution registry.
+        pub fn run(
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::protocol::PartRecord> {
+            let input = $crate::template::runner::read_input(DAY, options);
+            let (parsed, parse_record) =
+                $crate::template::runner::run_parse($parse, input, DAY, options);
+            let parsed = parsed.map(|parsed| &*Box::leak(Box::new(parsed)));
+            let mut records = vec![parse_record];
+            $(
+                records.push(match parsed {
+                    Some(parsed) => $crate::solution!(
+                        @part $func, $part, [$($name => $variant),*], parsed, options
+                    ),
+                    None => $crate::template::runner::skip_part(DAY, $part, options),
+                });
+            )*
+            $crate::template::runner::submit_records(&records, options, run);
+            records
+        }
+    };
+
+    // parts are called through a closure, so that e.g. a parsed `Vec<T>` is passed to a part taking `&[T]`.
+    (@part $func:expr, $part:expr, [], $input:expr, $options:expr) => {
+        $crate::template::runner::run_part(|input| $func(input), $input, DAY, $part, $options)
+    };
+
+    (@part $func:expr, $part:expr, [$($name:ident => $variant:expr),+], $input:expr, $options:expr) => {
+        $crate::template::runner::run_variants(
+            &[
+                $crate::template::runner::variant(
+                    $crate::template::runner::DEFAULT_VARIANT, $input, |input| $func(input)
+                ),
+                $( $crate::template::runner::variant(stringify!($name), $input, |input| $variant(input)), )+
+            ],
+            $input,
+            DAY,
+            $part,
+            $options,
+        )
+    };
+
+    (@common $day:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = $crate::template::runner::RunOptions::from_args();
+
+            // `--dhat` profiles the whole run into `dhat-heap.json`, `--memory` measures each part on its own instead.
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = (!options.memory).then(dhat::Profiler::new_heap);
+
+            run(&options);
         }
     };
 }
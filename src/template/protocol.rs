@@ -0,0 +1,373 @@
+/// Machine-readable result records exchanged between day binaries and `run_multi`.
+/// When `AOC_RESULTS_PATH` is set, every solved (or unsolved) part appends one JSON line to that file.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::environment::Environment;
+
+pub const RESULTS_PATH_ENV: &str = "AOC_RESULTS_PATH";
+
+/// Part number under which the timing of a shared parse step is recorded.
+pub const PARSE_PART: u8 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part panicked or ran past its timeout, see [`PartRecord::error`].
+    Failed,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            x => Err(format!("Unknown record status `{x}`.")),
+        }
+    }
+}
+
+/// Heap usage of a single run of a part, as measured by dhat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Most bytes that were live at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations.
+    pub allocations: u64,
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// The result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub median_nanos: f64,
+    pub std_dev_nanos: f64,
+    pub samples: u64,
+    pub status: Status,
+    /// Why a part failed, e.g. the panic message.
+    pub error: Option<String>,
+    /// Heap usage, for parts that ran with `--memory`.
+    pub memory: Option<MemoryStats>,
+    /// The machine and build of the binary that ran the part.
+    pub environment: Option<Environment>,
+}
+
+/// Append a record to the results file, if the parent process asked for one.
+pub fn emit(record: &PartRecord) -> io::Result<()> {
+    let Ok(path) = std::env::var(RESULTS_PATH_ENV) else {
+        return Ok(());
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a results file. A missing file yields no records.
+pub fn read_records(path: &Path) -> Result<Vec<PartRecord>, String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("Record is not valid JSON."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let memory = json.get("memory").map(MemoryStats::try_from).transpose()?;
+
+        let environment = json
+            .get("environment")
+            .map(Environment::try_from)
+            .transpose()?;
+
+        Ok(PartRecord {
+            day,
+            part: number("part")? as u8,
+            answer: answer.cloned(),
+            median_nanos: number("median_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            samples: number("samples")? as u64,
+            status,
+            error,
+            memory,
+            environment,
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_bytes: number("total_bytes")?,
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MemoryStats, PartRecord, Status, format_bytes};
+    use crate::day;
+    use crate::template::environment::Environment;
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn round_trips_records() {
+        let record = PartRecord {
+            day: day!(5),
+            part: 2,
+            answer: Some("a (tricky) answer\nwith lines".into()),
+            median_nanos: 1500.0,
+            std_dev_nanos: 20.0,
+            samples: 100,
+            status: Status::Solved,
+            error: None,
+            memory: None,
+            environment: None,
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = PartRecord::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn handles_unsolved_records() {
+        let json = r#"{ "day": "03", "part": 1, "answer": null, "median_nanos": 10, "std_dev_nanos": 0, "samples": 1, "status": "unsolved" }"#;
+        let record = PartRecord::try_from(&JsonValue::from_str(json).unwrap()).unwrap();
+        assert_eq!(record.day, day!(3));
+        assert_eq!(record.answer, None);
+        assert_eq!(record.status, Status::Unsolved);
+        assert_eq!(record.error, None);
+    }
+
+    #[test]
+    fn round_trips_failed_records() {
+        let record = PartRecord {
+            day: day!(6),
+            part: 1,
+            answer: None,
+            median_nanos: 2500.0,
+            std_dev_nanos: 0.0,
+            samples: 1,
+            status: Status::Failed,
+            error: Some("panicked: expected there to be at least 2 operands".into()),
+            memory: None,
+            environment: None,
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = PartRecord::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn round_trips_memory_stats_and_environments() {
+        let record = PartRecord {
+            day: day!(8),
+            part: 1,
+            answer: Some("42".into()),
+            median_nanos: 2500.0,
+            std_dev_nanos: 0.0,
+            samples: 1,
+            status: Status::Solved,
+            error: None,
+            memory: Some(MemoryStats {
+                total_bytes: 1_048_576,
+                peak_bytes: 4096,
+                allocations: 12,
+            }),
+            environment: Some(Environment {
+                cores: 8,
+                profile: "dhat".into(),
+                features: vec!["dhat-heap".into()],
+                ..Environment::default()
+            }),
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = PartRecord::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, record);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        let json = r#"{ "day": "03", "part": 1 }"#;
+        PartRecord::try_from(&JsonValue::from_str(json).unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+/// Picks the examples and their answers out of a puzzle description in `data/puzzles`.
+/// Descriptions follow a loose pattern: an example input is a code block introduced by a paragraph
+/// that mentions an example, and the answer for it is the last emphasized code span of the part.
+use std::mem;
+
+/// The example and expected answer of one part, either of which may be missing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    pub input: Option<String>,
+    /// Further examples of the part, e.g. a larger one. Their answers are not known.
+    pub extra_inputs: Vec<String>,
+    pub answer: Option<String>,
+}
+
+/// The examples of part one and, if it is already unlocked, part two.
+pub fn extract(markdown: &str) -> Vec<PartExample> {
+    sections(markdown)
+        .iter()
+        .map(|section| {
+            let mut inputs = example_inputs(section).into_iter();
+            PartExample {
+                input: inputs.next(),
+                extra_inputs: inputs.collect(),
+                answer: last_answer(section),
+            }
+        })
+        .collect()
+}
+
+/// Split the description into parts, each starting with a `## ` heading.
+fn sections(markdown: &str) -> Vec<String> {
+    let mut sections: Vec<String> = vec![];
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+        }
+
+        if !in_code && line.starts_with("## ") {
+            sections.push(String::new());
+        }
+
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+
+    sections
+}
+
+/// Code blocks of a section, together with the paragraph preceding them.
+fn code_blocks(section: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut paragraph = String::new();
+    let mut code: Option<String> = None;
+
+    for line in section.lines() {
+        match (&mut code, line.starts_with("```")) {
+            (None, true) => code = Some(String::new()),
+            (Some(block), true) => {
+                blocks.push((paragraph.clone(), mem::take(block)));
+                code = None;
+            }
+            (Some(block), false) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, false) if !line.trim().is_empty() => paragraph = line.into(),
+            (None, false) => {}
+        }
+    }
+
+    blocks
+}
+
+/// The distinct code blocks that are introduced as an example, e.g. by "For example:", in order.
+fn example_inputs(section: &str) -> Vec<String> {
+    let mut inputs: Vec<String> = vec![];
+
+    for (paragraph, block) in code_blocks(section) {
+        if paragraph.to_lowercase().contains("example")
+            && !block.trim().is_empty()
+            && !inputs.contains(&block)
+        {
+            inputs.push(block);
+        }
+    }
+
+    inputs
+}
+
+/// The last emphasized code span outside of code blocks, e.g. ``*`11`*``.
+fn last_answer(section: &str) -> Option<String> {
+    let mut in_code = false;
+
+    section
+        .lines()
+        .filter(|line| {
+            if line.starts_with("```") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        })
+        .flat_map(|line| {
+            line.split("*`")
+                .skip(1)
+                .filter_map(|rest| rest.split_once("`*").map(|(answer, _)| answer.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .last()
+}
+
+/// Fill the expected answer into the test of a part, if it still asserts `None`.
+/// With `example_part`, the test is also switched to the example file of that part, e.g. `01-2.txt`.
+/// Returns `None` if there was no test to fill in.
+pub fn fill_test(module: &str, part: u8, answer: &str, example_part: Option<u8>) -> Option<String> {
+    let name = match part {
+        1 => "fn test_part_one()",
+        _ => "fn test_part_two()",
+    };
+
+    let start = module.find(name)?;
+    let end = module[start..]
+        .find("\n    }")
+        .map_or(module.len(), |end| start + end);
+
+    let test = &module[start..end];
+    if !test.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let expected = if answer.parse::<i128>().is_ok() {
+        format!("Some({answer})")
+    } else {
+        format!("Some(String::from({answer:?}))")
+    };
+
+    let mut filled = test.replace(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, {expected});"),
+    );
+    if let Some(example_part) = example_part {
+        filled = filled.replace(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {example_part})"),
+        );
+    }
+
+    Some(format!("{}{filled}{}", &module[..start], &module[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartExample, extract, fill_test};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The lists are compared in pairs:
+
+```
+1 2
+```
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Pair up `3` and `3` for a distance of *`0`*.
+
+In the example above, the total distance is *`11`*.
+
+Here is a larger example:
+
+```
+1   1
+2   2
+```
+
+## --- Part Two ---
+
+This time, a list looks like this:
+
+```
+9   9
+```
+
+So, for these example lists, the similarity score at the end of this process is *`31`*.
+";
+
+    const MODULE: &str = "advent_of_code::solution!(1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+}
+";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        assert_eq!(
+            extract(PUZZLE),
+            vec![
+                PartExample {
+                    input: Some("3   4\n4   3\n2   5\n".into()),
+                    extra_inputs: vec!["1   1\n2   2\n".into()],
+                    answer: Some("11".into()),
+                },
+                PartExample {
+                    input: None,
+                    extra_inputs: vec![],
+                    answer: Some("31".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_part_one_only() {
+        let part_one = PUZZLE.split("## --- Part Two").next().unwrap();
+        assert_eq!(extract(part_one).len(), 1);
+        assert_eq!(extract(""), vec![]);
+    }
+
+    #[test]
+    fn fills_in_tests() {
+        let module = fill_test(MODULE, 1, "11", None).unwrap();
+        let module = fill_test(&module, 2, "abc,def", Some(2)).unwrap();
+
+        assert!(
+            module
+                .contains("read_file(\"examples\", DAY));\n        assert_eq!(result, Some(11));")
+        );
+        assert!(module.contains(
+            "read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(String::from(\"abc,def\")));"
+        ));
+
+        // tests that were already filled in are left alone.
+        assert_eq!(fill_test(&module, 1, "12", None), None);
+    }
+}
@@ -0,0 +1,469 @@
+/// Converts the HTML pages of adventofcode.com to markdown.
+/// Only the `<article>` elements are kept, which hold the puzzle description or the message after submitting an answer.
+/// The site uses a small set of tags, so this is a minimal parser rather than a general one.
+/// The markdown can in turn be rendered for the terminal, which understands the same small subset of markdown.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        href: Option<String>,
+        children: Vec<Node>,
+    },
+}
+
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+/// The markdown of all `<article>` elements of a page, separated by blank lines.
+pub fn articles(html: &str) -> String {
+    let nodes = parse(html);
+    let mut articles = vec![];
+    find_articles(&nodes, &mut articles);
+
+    let markdown: Vec<String> = articles
+        .into_iter()
+        .map(|children| {
+            let mut out = String::new();
+            render_blocks(children, &mut out);
+            out.trim().to_string()
+        })
+        .filter(|article| !article.is_empty())
+        .collect();
+
+    if markdown.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", markdown.join("\n\n"))
+    }
+}
+
+fn find_articles<'a>(nodes: &'a [Node], articles: &mut Vec<&'a [Node]>) {
+    for node in nodes {
+        if let Node::Element { name, children, .. } = node {
+            if name == "article" {
+                articles.push(children);
+            } else {
+                find_articles(children, articles);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse(html: &str) -> Vec<Node> {
+    // stack of open elements, the root collects top-level nodes.
+    let mut stack: Vec<(String, Option<String>, Vec<Node>)> = vec![(String::new(), None, vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+
+        let Some(tag) = rest.strip_prefix('<') else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = decode_entities(&rest[..end]);
+            stack.last_mut().unwrap().2.push(Node::Text(text));
+            rest = &rest[end..];
+            continue;
+        };
+
+        let Some((tag, after)) = tag.split_once('>') else {
+            break;
+        };
+        rest = after;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            // unmatched closing tags are ignored, unclosed elements are closed with their parent.
+            if let Some(index) = stack.iter().rposition(|(open, _, _)| *open == name)
+                && index > 0
+            {
+                while stack.len() > index {
+                    let (name, href, children) = stack.pop().unwrap();
+                    stack.last_mut().unwrap().2.push(Node::Element {
+                        name,
+                        href,
+                        children,
+                    });
+                }
+            }
+            continue;
+        }
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let href = attribute(tag, "href").map(|href| decode_entities(&href));
+
+        if VOID_ELEMENTS.contains(&name.as_str()) {
+            stack.last_mut().unwrap().2.push(Node::Element {
+                name,
+                href,
+                children: vec![],
+            });
+        } else {
+            stack.push((name, href, vec![]));
+        }
+    }
+
+    while stack.len() > 1 {
+        let (name, href, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element {
+            name,
+            href,
+            children,
+        });
+    }
+
+    stack.pop().unwrap().2
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}="))? + name.len() + 1;
+    let value = &tag[start..];
+
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next().map(Into::into),
+        _ => value.split_whitespace().next().map(Into::into),
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_blocks(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                if !text.trim().is_empty() {
+                    out.push_str(&collapse_whitespace(text));
+                }
+            }
+            Node::Element { name, children, .. } => match name.as_str() {
+                "h1" | "h2" | "h3" => {
+                    out.push_str(&format!("## {}\n\n", inline(children).trim()));
+                }
+                "p" => out.push_str(&format!("{}\n\n", inline(children).trim())),
+                "pre" => {
+                    let code = text_content(children);
+                    let newline = if code.ends_with('\n') { "" } else { "\n" };
+                    out.push_str(&format!("```\n{code}{newline}```\n\n"));
+                }
+                "ul" | "ol" => {
+                    for item in children {
+                        if let Node::Element { name, children, .. } = item
+                            && name == "li"
+                        {
+                            out.push_str(&format!("- {}\n", inline(children).trim()));
+                        }
+                    }
+                    out.push('\n');
+                }
+                "script" | "style" => {}
+                _ => render_blocks(children, out),
+            },
+        }
+    }
+}
+
+fn inline(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => collapse_whitespace(text),
+            Node::Element {
+                name,
+                href,
+                children,
+            } => match name.as_str() {
+                "em" | "i" | "strong" | "b" => format!("*{}*", inline(children)),
+                // emphasized code, e.g. an example answer, keeps its emphasis outside of the backticks.
+                "code" => match children.as_slice() {
+                    [Node::Element { name, children, .. }] if name == "em" => {
+                        format!("*`{}`*", text_content(children))
+                    }
+                    _ => format!("`{}`", text_content(children)),
+                },
+                "a" => match href {
+                    Some(href) => format!("[{}]({href})", inline(children)),
+                    None => inline(children),
+                },
+                "br" => "\n".into(),
+                "script" | "style" => String::new(),
+                _ => inline(children),
+            },
+        })
+        .collect()
+}
+
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => text_content(children),
+        })
+        .collect()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut was_space = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !was_space {
+                out.push(' ');
+            }
+            was_space = true;
+        } else {
+            out.push(c);
+            was_space = false;
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether a puzzle description already contains part two, which unlocks once part one is solved.
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown
+        .lines()
+        .any(|line| line.starts_with("## --- Part Two"))
+}
+
+/// Renders markdown written by [`articles`] with ANSI formatting.
+/// Headings and emphasized code, i.e. answers, are bold, emphasis is italic and code blocks are indented.
+pub fn to_terminal(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+
+        if in_code {
+            out.push_str(&format!("    {line}\n"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            out.push_str(&format!("  • {}\n", inline_to_terminal(item)));
+        } else {
+            out.push_str(&inline_to_terminal(line));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn inline_to_terminal(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut is_italic = false;
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['*', '`', '[']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some((code, after)) = rest
+            .strip_prefix("*`")
+            .and_then(|code| code.split_once("`*"))
+        {
+            out.push_str(&format!("{ANSI_BOLD}{code}{ANSI_RESET}"));
+            if is_italic {
+                out.push_str(ANSI_ITALIC);
+            }
+            rest = after;
+        } else if let Some((code, after)) =
+            rest.strip_prefix('`').and_then(|code| code.split_once('`'))
+        {
+            out.push_str(code);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('*')
+            && (is_italic || after.starts_with(|c: char| !c.is_whitespace()) && after.contains('*'))
+        {
+            // an asterisk followed by a space, e.g. in `2 * 3`, is kept as it is.
+            out.push_str(if is_italic { ANSI_RESET } else { ANSI_ITALIC });
+            is_italic = !is_italic;
+            rest = after;
+        } else if let Some((text, after)) = rest
+            .strip_prefix('[')
+            .and_then(|link| link.split_once("]("))
+            .and_then(|(text, after)| Some((text, after.split_once(')')?.1)))
+        {
+            out.push_str(&inline_to_terminal(text));
+            rest = after;
+        } else {
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+
+    out.push_str(rest);
+    if is_italic {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, has_part_two, to_terminal};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<!DOCTYPE html>
+<html><head><title>Day 1 - Advent of Code 2024</title></head><body>
+<header><h1 class="title-global"><a href="/">Advent of Code</a></h1></header>
+<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present &amp; accounted for.</p>
+<ul>
+<li>Pair up the <code>smallest</code> numbers.</li>
+<li>See the <a href="/2024/about" target="_blank">about page</a>.</li>
+</ul>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In the example above, this is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now, &lt;count&gt; them.</p></article>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present & accounted for.",
+            "",
+            "- Pair up the `smallest` numbers.",
+            "- See the [about page](/2024/about).",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "In the example above, this is *`11`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Now, <count> them.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(articles(html), expected);
+    }
+
+    #[test]
+    fn converts_submission_responses() {
+        let html = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>\n</main>";
+
+        assert_eq!(
+            articles(html),
+            "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data. [[Return to Day 1](/2024/day/1)]\n"
+        );
+        assert_eq!(articles("<main></main>"), "");
+    }
+
+    #[test]
+    fn renders_for_the_terminal() {
+        let markdown = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* has `3` lists, see [here](/2024/about). 2 * 3 = *`6`*.",
+            "",
+            "- *Pair up *`1`* and `2`.*",
+            "",
+            "```",
+            "3   4",
+            "*`x`*",
+            "```",
+            "",
+            "## --- Part Two ---",
+        ]
+        .join("\n");
+
+        let expected = [
+            format!("{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}"),
+            String::new(),
+            format!("The {ANSI_ITALIC}Chief Historian{ANSI_RESET} has 3 lists, see here. 2 * 3 = {ANSI_BOLD}6{ANSI_RESET}."),
+            String::new(),
+            format!("  • {ANSI_ITALIC}Pair up {ANSI_BOLD}1{ANSI_RESET}{ANSI_ITALIC} and 2.{ANSI_RESET}"),
+            String::new(),
+            "    3   4".into(),
+            "    *`x`*".into(),
+            String::new(),
+            format!("{ANSI_BOLD}--- Part Two ---{ANSI_RESET}"),
+            String::new(),
+        ]
+        .join("\n");
+
+        assert_eq!(to_terminal(&markdown), expected);
+        assert!(has_part_two(&markdown));
+        assert!(!has_part_two("## --- Day 1: Historian Hysteria ---\n"));
+    }
+}
@@ -1,11 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::protocol::format_bytes;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, benchmark_chart};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CHART_PATH: &str = "./.assets/benchmarks.svg";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -30,8 +32,8 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,25 +54,70 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Heap usage of a day, summed over its parts. The peak is the highest of any part.
+fn memory_cells(timing: &Timing) -> String {
+    let memory: Vec<_> = [&timing.parse, &timing.part_1, &timing.part_2]
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.memory)
+        .collect();
+
+    if memory.is_empty() {
+        return " `-` | `-` | `-` |".into();
+    }
+
+    format!(
+        " `{}` | `{}` | `{}` |",
+        format_bytes(memory.iter().map(|m| m.peak_bytes).max().unwrap_or(0)),
+        format_bytes(memory.iter().map(|m| m.total_bytes).sum()),
+        memory.iter().map(|m| m.allocations).sum::<u64>()
+    )
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // heap usage columns are only shown once `cargo time --memory --store` recorded some.
+    let has_memory = timings
+        .data
+        .iter()
+        .flat_map(|t| [&t.parse, &t.part_1, &t.part_2])
+        .any(|t| t.is_some_and(|t| t.memory.is_some()));
+
+    let (memory_header, memory_align) = if has_memory {
+        (
+            " Peak heap | Allocated | Allocations |",
+            " :---: | :---: | :---: |",
+        )
+    } else {
+        ("", "")
+    };
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("![Runtime per part, log scale]({CHART_PATH})"),
+        String::new(),
+        format!("| Day | Parse | Part 1 | Part 2 |{memory_header}"),
+        format!("| :---: | :---: | :---: | :---:  |{memory_align}"),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        let memory = if has_memory {
+            memory_cells(&timing)
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |{memory}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.parse.map_or("-".into(), |t| t.to_string()),
+            timing.part_1.map_or("-".into(), |t| t.to_string()),
+            timing.part_2.map_or("-".into(), |t| t.to_string())
         ));
     }
 
@@ -81,14 +128,22 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+/// Replace the block between two occurences of `marker` with `table`, or a single `marker` if the block is empty.
+pub fn replace_block(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    replace_block(s, MARKER, &table)
 }
 
+/// Write the benchmark chart and update the table in the readme.
 pub fn update(timings: Timings) -> Result<(), Error> {
+    write_chart(&timings)?;
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
@@ -97,31 +152,51 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     Ok(())
 }
 
+fn write_chart(timings: &Timings) -> Result<(), Error> {
+    let path = Path::new(CHART_PATH);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, benchmark_chart::render(timings))?;
+    Ok(())
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{MARKER, update_content};
+    use crate::{
+        day,
+        template::fixtures::millis,
+        template::protocol::MemoryStats,
+        template::timings::{Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: millis(10.0),
+                    part_2: millis(20.0),
                     total_nanos: 3e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: millis(30.0),
+                    part_2: millis(40.0),
                     total_nanos: 7e+10,
+                    environment: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    parse: millis(5.0),
+                    part_1: millis(40.0),
+                    part_2: millis(50.0),
                     total_nanos: 9e+10,
+                    environment: None,
                 },
             ],
         }
@@ -167,11 +242,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "![Runtime per part, log scale](./.assets/benchmarks.svg)",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `5.0ms` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +257,27 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+            total_bytes: 3072,
+            peak_bytes: 2048,
+            allocations: 3,
+        });
+        timings.data[0].part_2.as_mut().unwrap().memory = Some(MemoryStats {
+            total_bytes: 1024,
+            peak_bytes: 512,
+            allocations: 1,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(
+            s.contains("| Day | Parse | Part 1 | Part 2 | Peak heap | Allocated | Allocations |")
+        );
+        assert!(s.contains("| `-` | `10.0ms` | `20.0ms` | `2.0 KiB` | `4.0 KiB` | `4` |"));
+        assert!(s.contains("| `-` | `30.0ms` | `40.0ms` | `-` | `-` | `-` |"));
+    }
 }
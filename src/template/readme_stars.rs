@@ -0,0 +1,246 @@
+/// Module that updates the readme with ⭐️ progress.
+/// The table matches the one the `advent-readme-stars` workflow renders, but is built from local data:
+/// a part counts as solved once it has an answer in `data/answers.json` or was accepted according to `data/submissions.json`.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{Error, replace_block};
+use crate::template::submissions::Submissions;
+use crate::template::{Day, all_days, last_day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The stars earned on a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl DayStars {
+    pub fn count(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// Collect the stars of all days of the `year`'s event with at least one solved part.
+pub fn collect(answers: &Answers, submissions: &Submissions, year: u16) -> Vec<DayStars> {
+    // days solved before the ledger existed only have their answers recorded.
+    let is_solved =
+        |day: Day, part: u8| answers.get(day, part).is_some() || submissions.is_solved(day, part);
+
+    let last_day = last_day(i32::from(year));
+    let mut stars: Vec<DayStars> = all_days()
+        .take_while(|day| *day <= last_day)
+        .map(|day| DayStars {
+            day,
+            part_1: is_solved(day, 1),
+            part_2: is_solved(day, 2),
+        })
+        .collect();
+
+    // the second star of the last day is awarded for collecting all other stars, it has no answer.
+    let total: usize = stars.iter().map(DayStars::count).sum();
+    if let Some(last) = stars.last_mut()
+        && last.part_1
+        && !last.part_2
+        && total == 2 * usize::from(last_day.into_inner()) - 1
+    {
+        last.part_2 = true;
+    }
+
+    stars.retain(|s| s.count() > 0);
+    stars
+}
+
+fn construct_table(year: u16, stars: &[DayStars]) -> String {
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars {
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.day.into_inner(),
+            day.day.into_inner(),
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, stars: &[DayStars]) -> Result<(), Error> {
+    replace_block(s, MARKER, &construct_table(year, stars))
+}
+
+pub fn update(year: u16, stars: &[DayStars]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayStars, MARKER, collect, update_content};
+    use crate::day;
+    use crate::template::Day;
+    use crate::template::all_days;
+    use crate::template::answers::Answers;
+    use crate::template::submissions::{Outcome, Submission, Submissions};
+
+    fn submission(day: Day, part: u8, outcome: Outcome) -> Submission {
+        Submission {
+            day,
+            part,
+            answer: "42".into(),
+            timestamp: 0,
+            outcome,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(day!(1), 1, Outcome::Correct),
+                submission(day!(1), 2, Outcome::Correct),
+                submission(day!(3), 1, Outcome::Correct),
+                submission(day!(3), 2, Outcome::TooHigh),
+            ],
+        }
+    }
+
+    #[test]
+    fn collects_solved_days() {
+        assert_eq!(
+            collect(&Answers::default(), &get_mock_submissions(), 2024),
+            vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true
+                },
+                DayStars {
+                    day: day!(3),
+                    part_1: true,
+                    part_2: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn collects_stars_from_answers_and_submissions() {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 1, "42");
+
+        assert_eq!(
+            collect(&answers, &get_mock_submissions(), 2024),
+            vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true
+                },
+                DayStars {
+                    day: day!(2),
+                    part_1: true,
+                    part_2: false
+                },
+                DayStars {
+                    day: day!(3),
+                    part_1: true,
+                    part_2: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn awards_the_last_star_for_all_others() {
+        let mut submissions = Submissions::default();
+        for day in all_days() {
+            submissions.push(submission(day, 1, Outcome::Correct));
+            if day != day!(25) {
+                submissions.push(submission(day, 2, Outcome::Correct));
+            }
+        }
+
+        let stars = collect(&Answers::default(), &submissions, 2024);
+        assert_eq!(stars.iter().map(DayStars::count).sum::<usize>(), 50);
+
+        submissions.data.remove(0);
+        assert!(
+            !collect(&Answers::default(), &submissions, 2024)
+                .last()
+                .unwrap()
+                .part_2
+        );
+    }
+
+    #[test]
+    fn ends_the_event_after_12_days_from_2025() {
+        let mut submissions = Submissions::default();
+        for day in all_days() {
+            submissions.push(submission(day, 1, Outcome::Correct));
+            if day != day!(12) {
+                submissions.push(submission(day, 2, Outcome::Correct));
+            }
+        }
+
+        let stars = collect(&Answers::default(), &submissions, 2025);
+        assert_eq!(stars.len(), 12);
+        assert_eq!(stars.iter().map(DayStars::count).sum::<usize>(), 24);
+    }
+
+    #[test]
+    fn formats_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(
+            &mut s,
+            2024,
+            &collect(&Answers::default(), &get_mock_submissions(), 2024),
+        )
+        .unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, 2024, &[]).unwrap();
+        update_content(
+            &mut s,
+            2024,
+            &collect(&Answers::default(), &get_mock_submissions(), 2024),
+        )
+        .unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2024 Results").count(), 1);
+        assert!(s.contains("[Day 3]"));
+    }
+}
@@ -1,49 +1,169 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day,
+    protocol::{self, PartRecord, Status},
+    runner::{Output, RunOptions, Solution},
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+pub fn run_multi(
+    solutions: &[Solution],
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    options: &RunOptions,
+    jobs: usize,
+) -> Option<Timings> {
+    let results = run_days(solutions, days_to_run, is_release, options, jobs);
+    options.timed.then(|| summarize_timings(&results))
+}
 
-    let mut need_space = false;
+/// Collect the timings of a timed run and print their total.
+pub fn summarize_timings(results: &[(Day, Vec<PartRecord>)]) -> Timings {
+    let timings = collect_timings(results);
+    let total_millis = timings.total_millis();
+    println!("\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    timings
+}
 
+/// Collect the timings of a timed run.
+pub fn collect_timings(results: &[(Day, Vec<PartRecord>)]) -> Timings {
+    Timings {
+        data: results
+            .iter()
+            .filter(|(_, records)| !records.is_empty())
+            .map(|(day, records)| child_commands::timing_from_records(records, *day))
+            .collect(),
+    }
+}
+
+/// Run a set of days, in-process if the day is part of `solutions` and as a child binary otherwise.
+/// With `jobs > 1`, up to `jobs` days run at the same time. Their output is buffered and printed in day order.
+/// Output goes to `options.output`.
+pub fn run_days(
+    solutions: &[Solution],
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    options: &RunOptions,
+    jobs: usize,
+) -> Vec<(Day, Vec<PartRecord>)> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let results = if jobs > 1 {
+        run_parallel(solutions, &days, is_release, options, jobs)
+    } else {
+        days.iter()
+            .enumerate()
+            .map(|(i, &day)| {
+                if i > 0 {
+                    let _ = writeln!(options.output.clone());
+                }
+                run_day(solutions, day, is_release, options, &options.output)
+            })
+            .collect()
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    days.into_iter().zip(results).collect()
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
-
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+fn run_day(
+    solutions: &[Solution],
+    day: Day,
+    is_release: bool,
+    options: &RunOptions,
+    output: &Output,
+) -> Vec<PartRecord> {
+    let mut out = output.clone();
+    let _ = writeln!(out, "{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    let _ = writeln!(out, "------");
+
+    let options = RunOptions {
+        output: output.clone(),
+        ..options.clone()
+    };
+
+    // measuring heap usage needs the dhat allocator, so it always runs the day binary.
+    let records = match solutions
+        .iter()
+        .find(|s| s.day == day)
+        .filter(|_| !options.memory)
+    {
+        Some(solution) => (solution.run)(&options),
+        None => child_commands::run_solution(day, &options, is_release).unwrap(),
+    };
+
+    // the parse step of a solution is recorded as well, but does not count as solving anything.
+    if !records
+        .iter()
+        .any(|r| r.part != protocol::PARSE_PART && r.status == Status::Solved)
+    {
+        let _ = writeln!(out, "Not solved.");
     }
+
+    records
+}
+
+/// Run days on a pool of `jobs` worker threads. Each day is printed as soon as all days before it are done.
+fn run_parallel(
+    solutions: &[Solution],
+    days: &[Day],
+    is_release: bool,
+    options: &RunOptions,
+    jobs: usize,
+) -> Vec<Vec<PartRecord>> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let output = Output::buffer();
+                    let records = run_day(solutions, day, is_release, options, &output);
+                    let index = days.iter().position(|&d| d == day).unwrap();
+
+                    if tx.send((index, output.take(), records)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut results = Vec::with_capacity(days.len());
+
+        for (index, buf, records) in rx {
+            pending.insert(index, (buf, records));
+
+            while let Some((buf, records)) = pending.remove(&results.len()) {
+                let mut out = options.output.clone();
+                if !results.is_empty() {
+                    let _ = writeln!(out);
+                }
+                let _ = out.write_all(&buf);
+                results.push(records);
+            }
+        }
+
+        results
+    })
 }
 
 #[allow(dead_code)]
@@ -51,6 +171,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Protocol(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -65,130 +186,124 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, get_path_for_bin};
+    use crate::template::{
+        Day,
+        protocol::{self, PartRecord, Status},
+        runner::{Output, RunOptions},
+        timings::PartTiming,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        env, fs,
+        io::Write,
         path::Path,
-        process::{Command, Stdio},
-        thread,
+        process::{self, Command, Stdio},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, returning the result records it emitted.
+    /// When `options.output` is a buffer, the output of the child is captured into it instead of being forwarded.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        is_release: bool,
+    ) -> Result<Vec<PartRecord>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let mut args = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+
+        if options.memory {
+            args.extend([
+                "--profile".into(),
+                "dhat".into(),
+                "--features".into(),
+                "dhat-heap".into(),
+            ]);
+        } else if is_release {
+            args.push("--release".into());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        // mirror flags like `--time` to child invocations.
+        args.push("--".into());
+        args.extend(options.to_args());
+
+        // the child forwards its human-readable output to our stdout/stderr,
+        // while the structured results are written to a side-channel file.
+        let results_path =
+            env::temp_dir().join(format!("aoc-results-{}-{day}.jsonl", process::id()));
+        let _ = fs::remove_file(&results_path);
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args)
+            .env(protocol::RESULTS_PATH_ENV, &results_path);
+
+        match &options.output {
+            Output::Stdout => {
+                cmd.stdout(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .spawn()?
+                    .wait()?;
+            }
+            Output::Buffer(_) => {
+                let captured = cmd.output()?;
+                let mut out = options.output.clone();
+                out.write_all(&captured.stdout)?;
+                out.write_all(&captured.stderr)?;
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        let records = protocol::read_records(&results_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&results_path);
+        records
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn timing_from_records(records: &[PartRecord], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            // the records come from the binary that ran the day, which may have been built differently than this one.
+            environment: records
+                .iter()
+                .filter(|r| r.day == day)
+                .find_map(|r| r.environment.clone()),
         };
 
-        output
+        records
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+            .filter(|r| r.day == day && r.status == Status::Solved)
+            .for_each(|r| {
+                let timing = PartTiming {
+                    median_nanos: r.median_nanos,
+                    std_dev_nanos: (r.samples > 1).then_some(r.std_dev_nanos),
+                    samples: r.samples,
+                    memory: r.memory,
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                match r.part {
+                    protocol::PARSE_PART => timings.parse = Some(timing),
+                    1 => timings.part_1 = Some(timing),
+                    2 => timings.part_2 = Some(timing),
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.median_nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +320,87 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_records;
 
         use crate::day;
+        use crate::template::environment::Environment;
+        use crate::template::fixtures::record;
+        use crate::template::protocol::{PartRecord, Status};
+
+        #[test]
+        fn collects_execution_times() {
+            let res = timing_from_records(
+                &[
+                    record(1, 74.0, Status::Solved),
+                    record(2, 74_130_000.0, Status::Solved),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().to_string(), "74.0ns ± 1.0µs");
+            assert_eq!(res.part_2.unwrap().to_string(), "74.1ms ± 1.0µs");
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(0, 500.0, Status::Solved),
+                    record(1, 1000.0, Status::Solved),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 1500_f64);
+            assert_eq!(res.parse.unwrap().to_string(), "500.0ns ± 1.0µs");
+            assert_eq!(res.part_1.unwrap().to_string(), "1.0µs ± 1.0µs");
+        }
+
+        #[test]
+        fn formats_single_samples_without_spread() {
+            let res = timing_from_records(
+                &[PartRecord {
+                    samples: 1,
+                    ..record(1, 2e9, Status::Solved)
+                }],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2e9_f64);
+            assert_eq!(res.part_1.unwrap().to_string(), "2.0s");
+            assert_eq!(res.part_1.unwrap().std_dev_nanos, None);
+            assert_eq!(res.part_2, None);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_the_environment_of_the_day_binary() {
+            let environment = Environment {
+                profile: "release".into(),
+                ..Environment::default()
+            };
+            let res = timing_from_records(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(1, 10.0, Status::Solved),
+                    PartRecord {
+                        environment: Some(environment.clone()),
+                        ..record(2, 10.0, Status::Solved)
+                    },
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.environment, Some(environment));
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn skips_unsolved_parts() {
+            let res = timing_from_records(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(1, 10.0, Status::Unsolved),
+                    record(2, 10.0, Status::Unsolved),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
         }
     }
 }
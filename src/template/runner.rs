@@ -1,165 +1,1091 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, Write, stdin, stdout};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fmt, fs, process, thread};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{Answers, Verdict};
+use crate::template::environment::Environment;
+use crate::template::protocol::{self, MemoryStats, PartRecord, Status, format_bytes};
+use crate::template::submissions::{self, Outcome, Submission, Submissions};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, aoc_client, history, read_file, read_file_part,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// A day solution that can be called in-process, see the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    pub run: fn(&RunOptions) -> Vec<PartRecord>,
+}
+
+/// Where the human-readable output of a solution is written to.
+/// Buffered output is used when several days run in parallel and have to be printed in order.
+#[derive(Clone, Debug, Default)]
+pub enum Output {
+    #[default]
+    Stdout,
+    Buffer(Arc<Mutex<Vec<u8>>>),
+}
+
+impl Output {
+    pub fn buffer() -> Self {
+        Output::Buffer(Arc::default())
+    }
+
+    /// Take the buffered output, leaving the buffer empty. Always empty for [`Output::Stdout`].
+    pub fn take(&self) -> Vec<u8> {
+        match self {
+            Output::Stdout => vec![],
+            Output::Buffer(buf) => std::mem::take(&mut *buf.lock().unwrap()),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout => stdout().write(buf),
+            Output::Buffer(b) => b.lock().unwrap().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout => stdout().flush(),
+            Output::Buffer(_) => Ok(()),
+        }
+    }
+}
+
+/// The input a solution is run against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// The example in `data/examples`.
+    Example,
+    /// The example for a single part in `data/examples`, e.g. `01-2.txt`.
+    ExamplePart(u8),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Build the input source from the `--example`, `--example-part N` and `--input <path|->` flags.
+    pub fn from_flags(
+        example: bool,
+        example_part: Option<u8>,
+        input: Option<String>,
+    ) -> Result<Self, String> {
+        match (example, example_part, input) {
+            (false, None, None) => Ok(InputSource::Puzzle),
+            (true, None, None) => Ok(InputSource::Example),
+            (false, Some(part), None) => Ok(InputSource::ExamplePart(part)),
+            (false, None, Some(path)) if path == "-" => Ok(InputSource::Stdin),
+            (false, None, Some(path)) => Ok(InputSource::Path(path.into())),
+            _ => Err("`--example`, `--example-part` and `--input` are mutually exclusive.".into()),
+        }
+    }
+
+    pub fn read(&self, day: Day) -> String {
+        let contents = match self {
+            InputSource::Puzzle => return read_file("inputs", day),
+            InputSource::Example => return read_file("examples", day),
+            InputSource::ExamplePart(part) => return read_file_part("examples", day, part),
+            InputSource::Path(path) => fs::read_to_string(path),
+            InputSource::Stdin => io::read_to_string(stdin()),
+        };
+
+        contents.unwrap_or_else(|e| {
+            eprintln!("Could not read input from {self}: {e}");
+            process::exit(1);
+        })
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example => vec!["--example".into()],
+            InputSource::ExamplePart(part) => vec!["--example-part".into(), part.to_string()],
+            InputSource::Path(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::Example => write!(f, "example"),
+            InputSource::ExamplePart(part) => write!(f, "example for part {part}"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Options that control how solution parts are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every part instead of running it once.
+    pub timed: bool,
+    /// Submit the result of this part to adventofcode.com.
+    pub submit: Option<u8>,
+    /// Run the named variant instead of the default implementation, for parts that declare it.
+    pub variant: Option<String>,
+    /// Run every variant of a part and compare their answers and timings.
+    pub compare_variants: bool,
+    /// Wall-clock limit for a single run of a part. A part exceeding it is reported as failed.
+    pub timeout: Option<Duration>,
+    /// Measure the heap usage of every part. Only has an effect when built with the `dhat-heap` feature.
+    pub memory: bool,
+    pub input: InputSource,
+    pub output: Output,
+}
+
+impl RunOptions {
+    /// Parse the options from the arguments passed to a solution binary, e.g. `--time` or `--submit 1`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let submit = args.iter().position(|x| x == "--submit").map(|index| {
+            let Some(Ok(part)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let timeout = args.iter().position(|x| x == "--timeout").map(|index| {
+            let Some(Ok(secs)) = args.get(index + 1).map(|x| x.parse::<f64>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 10");
+                process::exit(1);
+            };
+            Duration::from_secs_f64(secs)
+        });
+
+        let example_part = args
+            .iter()
+            .position(|x| x == "--example-part")
+            .map(|index| {
+                let Some(Ok(part)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo solve 1 --example-part 2"
+                    );
+                    process::exit(1);
+                };
+                part
+            });
+
+        let input = InputSource::from_flags(
+            args.iter().any(|x| x == "--example"),
+            example_part,
+            args.iter()
+                .position(|x| x == "--input")
+                .and_then(|index| args.get(index + 1).cloned()),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        Self {
+            timed: args.iter().any(|x| x == "--time"),
+            submit,
+            variant: args
+                .iter()
+                .position(|x| x == "--variant")
+                .and_then(|index| args.get(index + 1).cloned()),
+            compare_variants: args.iter().any(|x| x == "--variants"),
+            timeout,
+            memory: args.iter().any(|x| x == "--memory"),
+            input,
+            output: Output::Stdout,
+        }
+    }
+
+    /// The inverse of [`RunOptions::from_args`], used to mirror options to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.timed {
+            args.push("--time".into());
+        }
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+        if let Some(variant) = &self.variant {
+            args.extend(["--variant".into(), variant.clone()]);
+        }
+        if self.compare_variants {
+            args.push("--variants".into());
+        }
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".into(), timeout.as_secs_f64().to_string()]);
+        }
+        if self.memory {
+            args.push("--memory".into());
+        }
+        args.extend(self.input.to_args());
+
+        args
+    }
+}
+
+/// Name of the implementation a part is declared with in `solution!`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// A named implementation of a part. See the `variants` form of `solution!`.
+pub type Variant<I, T> = (&'static str, fn(I) -> Option<T>);
+
+/// Accepted answers that results on the puzzle input are checked against, read once per process.
+static KNOWN_ANSWERS: OnceLock<Answers> = OnceLock::new();
+
+fn known_answers() -> &'static Answers {
+    KNOWN_ANSWERS.get_or_init(|| {
+        Answers::read_from_file().unwrap_or_else(|e| {
+            eprintln!("Failed to read answers: {e}");
+            process::exit(1);
+        })
+    })
+}
+
+/// Read the input selected in the options, printing where it came from.
+/// The input is leaked, so that parts can run on (and be abandoned on) their own thread.
+/// For the puzzle input, the accepted answers are read as well, so that a broken answers file fails before any part runs.
+pub fn read_input(day: Day, options: &RunOptions) -> &'static str {
+    if options.input == InputSource::Puzzle {
+        known_answers();
+    }
+
+    let _ = writeln!(
+        options.output.clone(),
+        "{ANSI_ITALIC}Input: {}{ANSI_RESET}",
+        options.input
+    );
+
+    options.input.read(day).leak()
+}
+
+/// Run, print and record a single part. A panicking part or one that runs past `options.timeout` is recorded as failed.
+pub fn run_part<I: Copy + Send + 'static, T: Display + Send + 'static>(
+    func: impl Fn(I) -> Option<T> + Send + 'static,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartRecord {
+    let (record, result) = run_labeled(func, input, day, part, &format!("Part {part}"), options);
+    finish_part(record)
+}
+
+/// A named implementation of a part, taking the same `input` as the default one.
+pub fn variant<I, T>(name: &'static str, _input: I, func: fn(I) -> Option<T>) -> Variant<I, T> {
+    (name, func)
+}
+
+/// Run a part that has several implementations, `variants[0]` being the default one.
+/// Depending on the options, this runs the default, the selected variant, or all variants side by side.
+/// Only the record of the variant that was selected is emitted.
+pub fn run_variants<I: Copy + Send + 'static, T: Display + PartialEq + Send + 'static>(
+    variants: &[Variant<I, T>],
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartRecord {
+    let selected = match options.variant.as_deref() {
+        Some(name) => variants
+            .iter()
+            .position(|(v, _)| *v == name)
+            .unwrap_or_else(|| {
+                let names: Vec<&str> = variants.iter().map(|(v, _)| *v).collect();
+                let _ = writeln!(
+                    options.output.clone(),
+                    "{ANSI_ITALIC}Part {part} has no variant `{name}`, running default (available: {}).{ANSI_RESET}",
+                    names.join(", ")
+                );
+                0
+            }),
+        None => 0,
+    };
+
+    if !options.compare_variants || variants.len() == 1 {
+        let (name, func) = variants[selected];
+        let label = if selected == 0 {
+            format!("Part {part}")
+        } else {
+            format!("Part {part} [{name}]")
+        };
+
+        let (record, result) = run_labeled(func, input, day, part, &label, options);
+        return finish_part(record);
+    }
+
+    let mut runs: Vec<(&str, PartRecord, Option<T>)> = variants
+        .iter()
+        .map(|&(name, func)| {
+            let label = format!("Part {part} [{name}]");
+            let (record, result) = run_labeled(func, input, day, part, &label, options);
+            (name, record, result)
+        })
+        .collect();
+
+    print_comparison(&mut options.output.clone(), part, &runs);
+
+    let (_, record, result) = runs.swap_remove(selected);
+    finish_part(record)
+}
+
+/// Run and print a single part, returning its (not yet emitted) record and result.
+fn run_labeled<I: Copy + Send + 'static, T: Display + Send + 'static>(
+    func: impl Fn(I) -> Option<T> + Send + 'static,
+    input: I,
+    day: Day,
+    part: u8,
+    label: &str,
+    options: &RunOptions,
+) -> (PartRecord, Option<T>) {
+    let mut out = options.output.clone();
+
+    let timed = run_timed(func, input, options, |result| {
+        print_result(&mut out, result, label, "");
+    });
+
+    let (result, stats) = match timed {
+        Ok(x) => x,
+        Err(failure) => {
+            print_failure(&mut out, label, &failure);
+            return (failure_record(day, part, &failure), None);
+        }
+    };
+
+    print_result(&mut out, &result, label, &format_duration(&stats));
+
+    if stats.samples > 1 {
+        print_stats(&mut out, &stats);
+    }
+    if let Some(memory) = &stats.memory {
+        print_memory(&mut out, memory);
+    }
+
+    let answer = result.as_ref().map(ToString::to_string);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // accepted answers only apply to the puzzle input.
+    if options.input == InputSource::Puzzle
+        && let Verdict::Mismatch { expected } = known_answers().verify(day, part, answer.as_deref())
+    {
+        let _ = writeln!(out, "  {ANSI_RED}✖ expected {expected}{ANSI_RESET}");
+    }
+
+    let status = if result.is_some() {
+        Status::Solved
+    } else {
+        Status::Unsolved
+    };
+
+    (build_record(day, part, answer, &stats, status), result)
+}
+
+fn finish_part(record: PartRecord) -> PartRecord {
+    emit_record(&record);
+    record
+}
+
+/// Submit the result of the part selected with `--submit`. Called by the `run` function of a solution once all parts ran.
+/// `run` is called again on the example, so that an answer that is also the example answer can be recognized.
+pub fn submit_records(
+    records: &[PartRecord],
+    options: &RunOptions,
+    run: fn(&RunOptions) -> Vec<PartRecord>,
+) {
+    let Some(part) = options.submit else {
+        return;
+    };
+
+    let Some((day, answer)) = records
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| Some((r.day, r.answer.as_deref()?)))
+    else {
+        eprintln!("Not submitting, part {part} has no result.");
+        return;
+    };
+
+    if options.input != InputSource::Puzzle {
+        eprintln!("Not submitting, the result is not for the puzzle input.");
+        return;
+    }
+
+    submit_result(day, part, answer, || {
+        example_answer(day, part, options, run)
+    });
+}
+
+/// The answer of a part for its example, if there is one.
+fn example_answer(
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+    run: fn(&RunOptions) -> Vec<PartRecord>,
+) -> Option<String> {
+    let examples = env::current_dir().ok()?.join("data").join("examples");
+    let input = if examples.join(format!("{day}-{part}.txt")).exists() {
+        InputSource::ExamplePart(part)
+    } else if examples.join(format!("{day}.txt")).exists() {
+        InputSource::Example
+    } else {
+        return None;
+    };
+
+    run(&RunOptions {
+        timeout: options.timeout,
+        input,
+        output: Output::buffer(),
+        ..RunOptions::default()
+    })
+    .into_iter()
+    .find(|r| r.part == part)?
+    .answer
+}
+
+fn print_comparison<T: PartialEq>(
+    out: &mut Output,
+    part: u8,
+    runs: &[(&str, PartRecord, Option<T>)],
+) {
+    let baseline = runs[0].1.median_nanos;
+
+    let _ = writeln!(out, "{ANSI_BOLD}Part {part} variants{ANSI_RESET}");
+    let _ = writeln!(
+        out,
+        "  {:<16} {:>20} {:>24} {:>8}",
+        "Variant", "Answer", "Median", "Relative"
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    for (name, record, _) in runs {
+        let timing = format_timing(
+            Duration::from_nanos(record.median_nanos as u64),
+            Duration::from_nanos(record.std_dev_nanos as u64),
+            u128::from(record.samples),
+        );
+        let _ = writeln!(
+            out,
+            "  {:<16} {:>20} {:>24} {:>7.2}×",
+            name,
+            record.answer.as_deref().unwrap_or("✖"),
+            timing,
+            record.median_nanos / baseline.max(1.0)
+        );
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if runs.iter().all(|(_, _, result)| *result == runs[0].2) {
+        let _ = writeln!(out, "  {ANSI_GREEN}✔ all variants agree{ANSI_RESET}");
+    } else {
+        let _ = writeln!(out, "  {ANSI_RED}✖ variants disagree{ANSI_RESET}");
+    }
+}
+
+/// Run the shared parse step of a solution. It is timed like a part and recorded as part [`protocol::PARSE_PART`].
+/// Yields no parsed value if the parse step failed.
+pub fn run_parse<T: Send + 'static>(
+    func: impl Fn(&'static str) -> T + Send + 'static,
+    input: &'static str,
+    day: Day,
+    options: &RunOptions,
+) -> (Option<T>, PartRecord) {
+    let mut out = options.output.clone();
+
+    let timed = run_timed(func, input, options, |_| {
+        let _ = write!(out, "Parse:");
+    });
+
+    let (parsed, stats) = match timed {
+        Ok(x) => x,
+        Err(failure) => {
+            print_failure(&mut out, "Parse", &failure);
+            let record = failure_record(day, protocol::PARSE_PART, &failure);
+            emit_record(&record);
+            return (None, record);
+        }
+    };
+
+    let _ = writeln!(out, "\rParse:{}", format_duration(&stats));
+
+    if stats.samples > 1 {
+        print_stats(&mut out, &stats);
+    }
+    if let Some(memory) = &stats.memory {
+        print_memory(&mut out, memory);
+    }
+
+    let record = build_record(day, protocol::PARSE_PART, None, &stats, Status::Solved);
+    emit_record(&record);
+    (Some(parsed), record)
+}
+
+/// Record a part that could not run because the shared parse step failed.
+pub fn skip_part(day: Day, part: u8, options: &RunOptions) -> PartRecord {
+    let failure = Failure {
+        reason: "skipped, parse step failed".into(),
+        elapsed: Duration::ZERO,
+    };
+
+    let _ = writeln!(
+        options.output.clone(),
+        "Part {part}: {ANSI_RED}✖ {}{ANSI_RESET}",
+        failure.reason
+    );
+
+    let record = failure_record(day, part, &failure);
+    emit_record(&record);
+    record
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn build_record(
+    day: Day,
+    part: u8,
+    answer: Option<String>,
+    stats: &BenchStats,
+    status: Status,
+) -> PartRecord {
+    PartRecord {
+        day,
+        part,
+        answer,
+        median_nanos: stats.median.as_nanos() as f64,
+        std_dev_nanos: stats.std_dev.as_nanos() as f64,
+        samples: stats.samples as u64,
+        status,
+        error: None,
+        memory: stats.memory,
+        environment: Some(Environment::current()),
+    }
+}
+
+fn failure_record(day: Day, part: u8, failure: &Failure) -> PartRecord {
+    PartRecord {
+        error: Some(failure.reason.clone()),
+        ..build_record(
+            day,
+            part,
+            None,
+            &BenchStats::from_samples(&[failure.elapsed]),
+            Status::Failed,
+        )
+    }
+}
+
+fn emit_record(record: &PartRecord) {
+    if let Err(e) = protocol::emit(record) {
+        eprintln!("Failed to write result record: {e}");
+    }
+}
+
+/// Summary statistics of a set of benchmark samples.
+#[derive(Clone, Debug)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR below Q1 or above Q3).
+    pub outliers: usize,
+    pub samples: u128,
+    /// Heap usage of the first run, see [`RunOptions::memory`].
+    pub memory: Option<MemoryStats>,
+}
+
+impl BenchStats {
+    /// Computes statistics for a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+
+        let n = nanos.len();
+        let median = if n.is_multiple_of(2) {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2
+        } else {
+            nanos[n / 2]
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let std_dev = if n > 1 {
+            let mean = nanos.iter().sum::<u128>() as f64 / n as f64;
+            let variance = nanos
+                .iter()
+                .map(|&x| (x as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&nanos, 25);
+        let q3 = percentile(&nanos, 75);
+        let iqr = q3 - q1;
+        let lower_fence = q1.saturating_sub(iqr * 3 / 2);
+        let upper_fence = q3 + iqr * 3 / 2;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            min: nanos_to_duration(nanos[0]),
+            median: nanos_to_duration(median),
+            p95: nanos_to_duration(percentile(&nanos, 95)),
+            std_dev: Duration::from_nanos(std_dev.round() as u64),
+            outliers: nanos
+                .iter()
+                .filter(|&&x| x < lower_fence || x > upper_fence)
+                .count(),
+            samples: n as u128,
+            memory: None,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[u128], p: usize) -> u128 {
+    let rank = (sorted.len() * p).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn nanos_to_duration(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+/// Default limit for each part when running many days, so that a single stuck part cannot hold up the rest.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Stack size of the thread a part runs on. Generous, as puzzle solutions like to recurse.
+const PART_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Set once a part timed out while its heap usage was measured for `--memory`. dhat allows a single profiler at a time,
+/// and the profiler of the abandoned part is only dropped when that part finishes.
+static PROFILER_ABANDONED: AtomicBool = AtomicBool::new(false);
+
+/// Why a part did not produce a result.
+struct Failure {
+    reason: String,
+    elapsed: Duration,
+}
+
+impl Failure {
+    fn panicked(payload: &(dyn Any + Send), elapsed: Duration) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".into());
+
+        Failure {
+            reason: format!("panicked: {message}"),
+            elapsed,
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
+///
+/// The part runs on its own thread, so that a panic or a run exceeding `options.timeout` can be reported as a [`Failure`].
+/// The timeout bounds the whole part: benching stops taking samples once it is reached.
+/// A run that timed out cannot be stopped: its thread is abandoned and keeps running until it finishes or the process exits.
+fn run_timed<I: Copy + Send + 'static, T: Send + 'static>(
+    func: impl Fn(I) -> T + Send + 'static,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    hook: impl FnOnce(&T),
+) -> Result<(T, BenchStats), Failure> {
+    let is_memory = cfg!(feature = "dhat-heap") && options.memory;
+    if is_memory && PROFILER_ABANDONED.load(Ordering::Relaxed) {
+        return Err(Failure {
+            reason: "skipped, the heap profiler is still held by a part that timed out".into(),
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    let (result_tx, result_rx) = mpsc::channel();
+    let (stats_tx, stats_rx) = mpsc::channel();
+    let is_timed = options.timed;
     let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    let deadline = options.timeout.map(|timeout| timer + timeout);
+
+    let worker = thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let timer = Instant::now();
+            let (result, memory) = {
+                // in memory mode, every part is measured on its own and the stats are read directly.
+                // otherwise, the profiler of `main` writes a profile of the whole run.
+                #[cfg(feature = "dhat-heap")]
+                let _profiler = is_memory.then(|| dhat::Profiler::builder().testing().build());
+
+                let result = func(input);
+                (result, heap_stats(is_memory))
+            };
+            let base_time = timer.elapsed();
+
+            // the receiver is gone if the part timed out, no point in benching it.
+            let stats = if result_tx.send((result, base_time)).is_ok() && is_timed {
+                bench(func, input, &base_time, deadline)
+            } else {
+                BenchStats::from_samples(&[base_time])
+            };
+
+            let _ = stats_tx.send(BenchStats { memory, ..stats });
+        })
+        .expect("could not spawn thread for solution part");
 
-        func(input)
+    let (result, base_time) = match recv_until(&result_rx, deadline) {
+        Ok(received) => received,
+        Err(RecvTimeoutError::Timeout) => {
+            if is_memory {
+                PROFILER_ABANDONED.store(true, Ordering::Relaxed);
+            }
+            return Err(Failure {
+                reason: format!("timed out after {:.1?}", timer.elapsed()),
+                elapsed: timer.elapsed(),
+            });
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let payload = worker.join().expect_err("part exited without a result");
+            return Err(Failure::panicked(payload.as_ref(), timer.elapsed()));
+        }
     };
-    let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if is_timed {
+        let mut out = options.output.clone();
+        let _ = write!(out, " > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = out.flush();
+    }
 
-    (result, run.0, run.1)
+    // benching stops at the deadline, give the sample in progress about as long as the first run took to finish.
+    let grace = base_time * 2 + Duration::from_millis(10);
+    match recv_until(&stats_rx, deadline.map(|deadline| deadline + grace)) {
+        Ok(stats) => Ok((result, stats)),
+        // a single sample ran past the deadline, fall back to the timing of the first run.
+        Err(RecvTimeoutError::Timeout) => Ok((result, BenchStats::from_samples(&[base_time]))),
+        Err(RecvTimeoutError::Disconnected) => {
+            let payload = worker.join().expect_err("part exited without its timings");
+            Err(Failure::panicked(payload.as_ref(), timer.elapsed()))
+        }
+    }
+}
+
+/// Wait for a message from a part, giving up at the `deadline` if there is one.
+fn recv_until<T>(rx: &mpsc::Receiver<T>, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+    match deadline {
+        Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    }
+}
+
+/// Heap usage since the profiler was started, if requested.
+#[cfg(feature = "dhat-heap")]
+fn heap_stats(is_memory: bool) -> Option<MemoryStats> {
+    is_memory.then(|| {
+        let stats = dhat::HeapStats::get();
+        MemoryStats {
+            total_bytes: stats.total_bytes,
+            peak_bytes: stats.max_bytes as u64,
+            allocations: stats.total_blocks,
+        }
+    })
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_stats(_is_memory: bool) -> Option<MemoryStats> {
+    None
+}
+
+/// Run `func` and count the heap allocations it makes, see the `assert_allocations!` macro.
+/// Without the `dhat-heap` feature, there is no allocator to count with and `None` is returned.
+#[cfg(feature = "dhat-heap")]
+pub fn count_allocations<T>(func: impl FnOnce() -> T) -> (T, Option<u64>) {
+    // only one profiler can be running at a time, but tests run in parallel.
+    static PROFILER: Mutex<()> = Mutex::new(());
+    let _lock = PROFILER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let result = func();
+    (result, Some(dhat::HeapStats::get().total_blocks))
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+#[cfg(not(feature = "dhat-heap"))]
+pub fn count_allocations<T>(func: impl FnOnce() -> T) -> (T, Option<u64>) {
+    (func(), None)
+}
 
+/// Bench a solution part. A number of untimed warm-up iterations (a tenth of the sample count) is run first,
+/// so that cold caches and lazy initialisation do not show up in the measured samples.
+/// No further iterations are started once the `deadline` has passed, the samples taken so far are kept.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    deadline: Option<Instant>,
+) -> BenchStats {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let is_expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+    for _ in 0..bench_iterations / 10 {
+        if is_expired() {
+            break;
+        }
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        if is_expired() {
+            break;
+        }
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    if timers.is_empty() {
+        timers.push(*base_time);
+    }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    BenchStats::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Formats a timing as `median ± std_dev`, or as a bare duration if it was measured once.
+pub fn format_timing(median: Duration, std_dev: Duration, samples: u128) -> String {
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!("{median:.1?}")
+    } else {
+        format!("{median:.1?} ± {std_dev:.1?}")
+    }
+}
+
+fn format_duration(stats: &BenchStats) -> String {
+    let timing = format_timing(stats.median, stats.std_dev, stats.samples);
+
+    if stats.samples == 1 {
+        format!(" ({timing})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({timing} @ {} samples)", stats.samples)
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_stats(out: &mut Output, stats: &BenchStats) {
+    let BenchStats {
+        min, p95, outliers, ..
+    } = stats;
+
+    let _ = writeln!(
+        out,
+        "  {ANSI_ITALIC}min {min:.1?} · p95 {p95:.1?} · {outliers} outliers{ANSI_RESET}"
+    );
+}
+
+fn print_memory(out: &mut Output, memory: &MemoryStats) {
+    let _ = writeln!(
+        out,
+        "  {ANSI_ITALIC}heap {} peak · {} total · {} allocations{ANSI_RESET}",
+        format_bytes(memory.peak_bytes),
+        format_bytes(memory.total_bytes),
+        memory.allocations
+    );
+}
+
+fn print_failure(out: &mut Output, label: &str, failure: &Failure) {
+    let _ = writeln!(
+        out,
+        "\r{label}: {ANSI_RED}✖ {}{ANSI_RESET} ({:.1?})",
+        failure.reason, failure.elapsed
+    );
+}
+
+fn print_result<T: Display>(out: &mut Output, result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
+    let _ = match result {
         Some(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    write!(out, "{str}")
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    write!(out, "\r{str}\n{result}\n")
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    write!(out, "{str}")
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    write!(out, "\r{str}\n")
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                write!(out, "{part}: ✖")
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                write!(out, "\r{part}: ✖             \n")
             }
         }
-    }
+    };
+    let _ = out.flush();
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Submit one part of the solution to adventofcode.com and record the response in the submission ledger.
+/// Answers the ledger proves wrong are refused, and bogus looking ones have to be confirmed.
+fn submit_result(
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    answer: &str,
+    example_answer: impl FnOnce() -> Option<String>,
+) {
+    let mut submissions = match Submissions::read_from_file() {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("{ANSI_RED}Not submitting, failed to read submissions: {e}{ANSI_RESET}");
+            return;
+        }
+    };
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    if let Some(reason) = submissions.refusal(day, part, answer) {
+        eprintln!("{ANSI_RED}Not submitting, {reason}{ANSI_RESET}");
+        return;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Some(reason) = submissions::suspicion(answer, example_answer().as_deref())
+        && !confirm(&format!("{reason} Submit anyway?"))
+    {
+        eprintln!("Not submitting.");
+        return;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result...");
+    let outcome = match aoc_client::submit(day, part, answer) {
+        Ok(message) => Outcome::classify(&message),
+        Err(e) => {
+            eprintln!("failed to submit day {day} part {part}: {e}");
+            process::exit(1);
+        }
+    };
+
+    submissions.push(Submission {
+        day,
+        part,
+        answer: answer.into(),
+        timestamp: history::now(),
+        outcome,
+    });
+    if let Err(e) = submissions.store_file() {
+        eprintln!("Failed to record the submission: {e}");
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if outcome == Outcome::Correct {
+        match Answers::read_from_file() {
+            Ok(mut answers) => {
+                answers.set(day, part, answer);
+                if let Err(e) = answers.store_file() {
+                    eprintln!("Failed to record the accepted answer: {e}");
+                }
+            }
+            Err(e) => eprintln!("Failed to record the accepted answer: {e}"),
+        }
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    stdin().read_line(&mut line).is_ok() && line.trim().eq_ignore_ascii_case("y")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, RunOptions, bench, run_timed};
+    use std::{
+        thread,
+        time::{Duration, Instant},
     };
 
-    if part_submit != part {
-        return None;
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|&x| Duration::from_nanos(x)).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_single_sample() {
+        let stats = BenchStats::from_samples(&samples(&[42]));
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.samples, 1);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn computes_median_of_even_samples() {
+        let stats = BenchStats::from_samples(&samples(&[40, 10, 30, 20]));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.p95, Duration::from_nanos(40));
+        assert_eq!(stats.samples, 4);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn ignores_outlier_in_median() {
+        let stats = BenchStats::from_samples(&samples(&[
+            100, 101, 99, 100, 102, 98, 100, 101, 99, 100, 10_000,
+        ]));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.outliers, 1);
+        assert!(stats.std_dev > Duration::from_nanos(1000));
+    }
+
+    #[test]
+    fn stops_benching_at_deadline() {
+        let sleep = Duration::from_millis(5);
+        let deadline = Instant::now() + Duration::from_millis(30);
+        let stats = bench(|d| thread::sleep(d), sleep, &sleep, Some(deadline));
+        assert!(stats.samples < 10);
+    }
+
+    #[test]
+    fn bounds_benched_part_by_timeout() {
+        let options = RunOptions {
+            timed: true,
+            timeout: Some(Duration::from_millis(100)),
+            ..RunOptions::default()
+        };
+
+        let timer = Instant::now();
+        let timed = run_timed(
+            |d| thread::sleep(d),
+            Duration::from_millis(20),
+            &options,
+            |()| {},
+        );
+        assert!(timed.is_ok());
+        assert!(timer.elapsed() < Duration::from_secs(1));
+    }
 }
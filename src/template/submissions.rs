@@ -0,0 +1,420 @@
+/// Ledger of answers submitted to adventofcode.com, stored in `data/submissions.json`.
+/// It is used to refuse answers that are already known to be wrong before they are sent,
+/// as every wrong answer makes the site lock out further submissions for a while.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::history::format_timestamp;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// How adventofcode.com responded to a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The answer was not checked because the last submission was too recent.
+    RateLimited,
+    /// A response that was not recognized, e.g. for a part that was already solved.
+    Unknown,
+}
+
+impl Outcome {
+    /// Classify the message of a submission response.
+    pub fn classify(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("answer is too high") {
+            Outcome::TooHigh
+        } else if message.contains("answer is too low") {
+            Outcome::TooLow
+        } else if message.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::RateLimited
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::Unknown => "not recognized",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            "rate_limited" => Ok(Outcome::RateLimited),
+            "unknown" => Ok(Outcome::Unknown),
+            x => Err(format!("Unknown submission outcome `{x}`.")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub outcome: Outcome,
+}
+
+/// All submissions, in the order they were made.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty ledger.
+    /// A file that cannot be parsed is an error, so that it is not overwritten by the next submission.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(contents) => Submissions::try_from(contents)
+                .map_err(|e| format!("could not parse \"{SUBMISSIONS_FILE_PATH}\": {e}")),
+            Err(_) => Ok(Submissions::default()),
+        }
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.data.push(submission);
+    }
+
+    /// Whether a part was accepted.
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.solved_at(day, part).is_some()
+    }
+
+    /// When a part was accepted, in seconds since the unix epoch.
+    pub fn solved_at(&self, day: Day, part: u8) -> Option<u64> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.outcome == Outcome::Correct)
+            .map(|s| s.timestamp)
+    }
+
+    /// Why the ledger proves `answer` wrong, if it does.
+    /// An answer is wrong if the part is already solved with another answer, if the same answer was rejected before,
+    /// or if it is on the wrong side of an answer that was too high or too low.
+    pub fn refusal(&self, day: Day, part: u8, answer: &str) -> Option<String> {
+        let answer = answer.trim();
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Some(if correct.answer == answer {
+                format!("{answer} was already accepted.")
+            } else {
+                format!("the part was already solved with {}.", correct.answer)
+            });
+        }
+
+        if let Some(previous) = submissions
+            .iter()
+            .find(|s| s.answer == answer && is_rejection(s.outcome))
+        {
+            return Some(format!(
+                "{answer} was already submitted on {} UTC and was {}.",
+                format_timestamp(previous.timestamp),
+                previous.outcome.describe()
+            ));
+        }
+
+        let value: i128 = answer.parse().ok()?;
+        let bound = |outcome: Outcome| {
+            submissions
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Outcome::TooHigh).min()
+            && value >= high
+        {
+            return Some(format!("{answer} is not below {high}, which was too high."));
+        }
+
+        if let Some(low) = bound(Outcome::TooLow).max()
+            && value <= low
+        {
+            return Some(format!("{answer} is not above {low}, which was too low."));
+        }
+
+        None
+    }
+}
+
+fn is_rejection(outcome: Outcome) -> bool {
+    matches!(outcome, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+}
+
+/// Why an answer looks bogus and should be confirmed before it is submitted, if it does.
+pub fn suspicion(answer: &str, example_answer: Option<&str>) -> Option<String> {
+    let answer = answer.trim();
+
+    if answer.is_empty() || answer.trim_start_matches('-') == "0" {
+        Some(format!("`{answer}` does not look like a real answer."))
+    } else if example_answer.is_some_and(|example| example.trim() == answer) {
+        Some(format!("`{answer}` is also the answer for the example."))
+    } else {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part: number("part")? as u8,
+            answer: answer.clone(),
+            timestamp: number("timestamp")? as u64,
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Submission, Submissions, suspicion};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            timestamp: 1_733_029_200,
+            outcome,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "5000", Outcome::TooHigh),
+                submission(1, "100", Outcome::TooLow),
+                submission(1, "4000", Outcome::TooHigh),
+                submission(1, "abc", Outcome::Wrong),
+                submission(1, "300", Outcome::RateLimited),
+            ],
+        }
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer to finding the Chief Historian.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 41s left to wait.",
+                Outcome::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Outcome::Unknown,
+            ),
+        ];
+
+        for (message, outcome) in cases {
+            assert_eq!(Outcome::classify(message), outcome, "{message}");
+        }
+    }
+
+    #[test]
+    fn refuses_answers_proven_wrong() {
+        let submissions = get_mock_submissions();
+
+        assert!(submissions.refusal(day!(1), 1, "abc").is_some());
+        assert!(submissions.refusal(day!(1), 1, "4000").is_some());
+        assert!(submissions.refusal(day!(1), 1, "4500").is_some());
+        assert!(submissions.refusal(day!(1), 1, "99").is_some());
+        assert!(submissions.refusal(day!(1), 1, "100").is_some());
+
+        // rate-limited submissions were never checked.
+        assert_eq!(submissions.refusal(day!(1), 1, "300"), None);
+        assert_eq!(submissions.refusal(day!(1), 1, "3999"), None);
+        assert_eq!(submissions.refusal(day!(1), 2, "5000"), None);
+        assert_eq!(submissions.refusal(day!(2), 1, "abc"), None);
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = get_mock_submissions();
+        assert_eq!(submissions.solved_at(day!(1), 1), None);
+
+        submissions.push(submission(1, "1234", Outcome::Correct));
+        assert_eq!(submissions.solved_at(day!(1), 1), Some(1_733_029_200));
+
+        assert_eq!(
+            submissions.refusal(day!(1), 1, "1234"),
+            Some("1234 was already accepted.".into())
+        );
+        assert_eq!(
+            submissions.refusal(day!(1), 1, "1235"),
+            Some("the part was already solved with 1234.".into())
+        );
+    }
+
+    #[test]
+    fn suspects_bogus_answers() {
+        assert!(suspicion("0", None).is_some());
+        assert!(suspicion("", None).is_some());
+        assert!(suspicion("11", Some("11")).is_some());
+        assert_eq!(suspicion("1234", Some("11")), None);
+        assert_eq!(suspicion("10", None), None);
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, submissions.data);
+    }
+}
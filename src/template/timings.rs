@@ -1,17 +1,114 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::environment::Environment;
+use crate::template::protocol::{MemoryStats, PARSE_PART};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON format written by [`Timings::store_file`].
+/// Files without a version (version 1) store parts as formatted strings and are migrated when read.
+const TIMINGS_VERSION: f64 = 2.0;
+
+/// Represents the benchmark of a single part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    pub median_nanos: f64,
+    /// Standard deviation of the samples, if the part was measured more than once.
+    pub std_dev_nanos: Option<f64>,
+    /// Number of samples, `0` if unknown because the timing was migrated from version 1.
+    pub samples: u64,
+    /// Heap usage, recorded by `cargo time --memory`.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartTiming {
+    /// Parses a timing as stored by version 1, e.g. `1.2ms ± 20.0µs`.
+    fn from_formatted(timing: &str) -> Option<Self> {
+        let mut split = timing.split('±');
+        let median_nanos = parse_nanos(split.next()?)?;
+        let std_dev_nanos = match split.next() {
+            Some(std_dev) => Some(parse_nanos(std_dev)?),
+            None => None,
+        };
+
+        Some(PartTiming {
+            median_nanos,
+            std_dev_nanos,
+            samples: 0,
+            memory: None,
+        })
+    }
+}
+
+impl fmt::Display for PartTiming {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let median = Duration::from_nanos(self.median_nanos as u64);
+        match self.std_dev_nanos {
+            Some(std_dev) => write!(
+                f,
+                "{median:.1?} ± {:.1?}",
+                Duration::from_nanos(std_dev as u64)
+            ),
+            None => write!(f, "{median:.1?}"),
+        }
+    }
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Timing of the shared parse step, for solutions that declare one.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// The machine and toolchain the day was timed with.
+    pub environment: Option<Environment>,
+}
+
+impl Timing {
+    /// Timing of a part, [`PARSE_PART`] being the parse step.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            PARSE_PART => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> Option<&mut PartTiming> {
+        match part {
+            PARSE_PART => self.parse.as_mut(),
+            1 => self.part_1.as_mut(),
+            2 => self.part_2.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Median nanoseconds of a part, [`PARSE_PART`] being the parse step.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).map(|t| t.median_nanos)
+    }
+}
+
+/// The change of a single part between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingChange {
+    pub day: Day,
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+}
+
+impl TimingChange {
+    pub fn percent(&self) -> f64 {
+        (self.after_nanos - self.before_nanos) / self.before_nanos.max(1.0) * 100.0
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -38,11 +135,24 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Heap usage is kept for parts that are re-timed without it.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                for part in [PARSE_PART, 1, 2] {
+                    if let (Some(new), Some(old)) = (timing.part_mut(part), old.part(part))
+                        && new.memory.is_none()
+                    {
+                        new.memory = old.memory;
+                    }
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +161,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -60,6 +170,53 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Attach heap usage to the timed parts. Parts without a timing are skipped.
+    /// Returns the updated timings and how many parts were updated.
+    pub fn with_memory(&self, memory: &[(Day, u8, MemoryStats)]) -> (Self, usize) {
+        let mut timings = self.clone();
+        let mut updated = 0;
+
+        for (day, part, stats) in memory {
+            if let Some(timing) = timings
+                .data
+                .iter_mut()
+                .find(|t| t.day == *day)
+                .and_then(|t| t.part_mut(*part))
+            {
+                timing.memory = Some(*stats);
+                updated += 1;
+            }
+        }
+
+        (timings, updated)
+    }
+
+    /// Compare `self` as the baseline with `new`, for every part that is timed in both.
+    pub fn compare(&self, new: &Self) -> Vec<TimingChange> {
+        let mut changes = vec![];
+
+        for after in &new.data {
+            let Some(before) = self.data.iter().find(|t| t.day == after.day) else {
+                continue;
+            };
+
+            for part in [PARSE_PART, 1, 2] {
+                if let (Some(before_nanos), Some(after_nanos)) =
+                    (before.part_nanos(part), after.part_nanos(part))
+                {
+                    changes.push(TimingChange {
+                        day: after.day,
+                        part,
+                        before_nanos,
+                        after_nanos,
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -67,12 +224,29 @@ impl Timings {
     }
 }
 
+/// Parses a duration formatted like `1.2ms` to nanoseconds.
+fn parse_nanos(duration: &str) -> Option<f64> {
+    let duration = duration.trim();
+    let (value, unit) = duration.split_at(duration.find(|c: char| c.is_alphabetic())?);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    value.parse::<f64>().ok().map(|x| x * factor)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +262,23 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: version 1 files do not have a version, their parts are migrated by `Timing::try_from`.
+        let version = json
+            .get("version")
+            .map_or(Some(&1.0), |v| v.get::<f64>())
+            .ok_or("expected `json.version` to be a number.")?;
+
+        if *version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings version {version} is newer than the supported version {TIMINGS_VERSION}."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -113,25 +301,23 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, part) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
 
         JsonValue::Object(map)
     }
@@ -151,65 +337,115 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            // NOTE: version 1 stored formatted strings.
+            Some(JsonValue::String(x)) => PartTiming::from_formatted(x)
+                .map(Some)
+                .ok_or(format!("Expected timing.{key} to be a formatted duration.")),
+            Some(x) => PartTiming::try_from(x).map(Some),
+        };
+
+        // NOTE: `parse` was added later, older files do not contain it.
+        let parse = part("parse")?;
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        if !json.contains_key("part_1") || !json.contains_key("part_2") {
+            return Err("Expected timing to have keys `part_1` and `part_2`.".into());
+        }
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: `environment` was added later, older files do not contain it.
+        let environment = json
+            .get("environment")
+            .map(Environment::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            environment,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
-mod tests {
-    use crate::day;
-
-    use super::{Timing, Timings};
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
-            ],
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            match value.std_dev_nanos {
+                Some(x) => JsonValue::Number(x),
+                None => JsonValue::Null,
+            },
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
         }
+
+        JsonValue::Object(map)
     }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be null, string or object.")?;
+
+        let median_nanos = json
+            .get("median_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.median_nanos to be a number.")?;
+
+        let std_dev_nanos = json
+            .get("std_dev_nanos")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected part.std_dev_nanos to be null or number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.samples to be a number.")?;
+
+        // NOTE: only parts measured with `--memory` have heap usage.
+        let memory = json.get("memory").map(MemoryStats::try_from).transpose()?;
+
+        Ok(PartTiming {
+            median_nanos,
+            std_dev_nanos: std_dev_nanos.copied(),
+            samples: samples as u64,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::template::fixtures::{get_mock_timings, millis};
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -218,11 +454,61 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_nanos(1), Some(1e6));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_numeric_json_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "median_nanos": 1000000, "std_dev_nanos": 2000, "samples": 100 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    median_nanos: 1e6,
+                    std_dev_nanos: Some(2000.0),
+                    samples: 100,
+                    memory: None,
+                })
+            );
+        }
+
+        #[test]
+        fn migrates_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms ± 20.0µs", "part_2": "74.0ns", "total_nanos": 1500074 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, None);
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    median_nanos: 1.5e6,
+                    std_dev_nanos: Some(20_000.0),
+                    samples: 0,
+                    memory: None,
+                })
+            );
+            assert_eq!(timing.part_nanos(2), Some(74.0));
+        }
+
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "11", "parse": "12.0µs", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3012000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_nanos(0), Some(12_000.0));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +533,8 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::environment::Environment;
+        use crate::template::timings::{TIMINGS_VERSION, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -265,6 +553,33 @@ mod tests {
                     .len(),
                 3
             );
+            assert_eq!(
+                value
+                    .get::<HashMap<String, JsonValue>>()
+                    .unwrap()
+                    .get("version")
+                    .unwrap()
+                    .get::<f64>(),
+                Some(&TIMINGS_VERSION)
+            );
+        }
+
+        #[test]
+        fn round_trips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].environment = Some(Environment {
+                cores: 4,
+                profile: "release".into(),
+                ..Environment::default()
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), timings.data.len());
+            assert_eq!(parsed.data[0].environment, timings.data[0].environment);
+            assert_eq!(parsed.data[1].environment, None);
+            assert_eq!(parsed.data[2].part_1, timings.data[2].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
         }
     }
 
@@ -274,18 +589,22 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::millis;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: millis(1.0),
+                    part_2: millis(2.0),
                     total_nanos: 3_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +612,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: millis(1.0),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,23 +628,77 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    environment: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings, parse_nanos},
+        };
+
+        use super::{get_mock_timings, millis};
+
+        #[test]
+        fn parses_formatted_timings() {
+            assert_eq!(parse_nanos("74.0ns"), Some(74.0));
+            assert_eq!(parse_nanos("1.5µs"), Some(1500.0));
+            assert_eq!(parse_nanos(" 20.0µs"), Some(20_000.0));
+            assert_eq!(parse_nanos("2.0s"), Some(2e9));
+            assert_eq!(parse_nanos("10ms"), Some(1e7));
+            assert_eq!(parse_nanos("fast"), None);
+        }
+
+        #[test]
+        fn compares_parts_timed_in_both() {
+            let baseline = get_mock_timings();
+            let new = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(2),
+                        parse: millis(1.0),
+                        part_1: millis(33.0),
+                        part_2: millis(20.0),
+                        total_nanos: 5.4e7,
+                        environment: None,
+                    },
+                    Timing {
+                        day: day!(3),
+                        parse: None,
+                        part_1: millis(1.0),
+                        part_2: None,
+                        total_nanos: 1e6,
+                        environment: None,
+                    },
+                ],
+            };
+
+            let changes = baseline.compare(&new);
+            assert_eq!(changes.len(), 2);
+            assert_eq!((changes[0].day, changes[0].part), (day!(2), 1));
+            assert!((changes[0].percent() - 10.0).abs() < 1e-9);
+            assert_eq!((changes[1].day, changes[1].part), (day!(2), 2));
+            assert!((changes[1].percent() + 50.0).abs() < 1e-9);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::protocol::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, millis};
 
         #[test]
         fn handles_disjunct_timings() {
@@ -331,9 +706,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +728,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    environment: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -380,5 +759,25 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_memory_of_retimed_parts() {
+            let memory = MemoryStats {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 2,
+            };
+            let (timings, updated) = get_mock_timings().with_memory(&[
+                (day!(2), 1, memory),
+                (day!(2), 2, memory),
+                (day!(4), 2, memory),
+            ]);
+            assert_eq!(updated, 2);
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].part_1.unwrap().memory, Some(memory));
+            assert_eq!(merged.data[1].part_2.unwrap().memory, Some(memory));
+            assert_eq!(merged.data[0].part_1.unwrap().memory, None);
+        }
     }
 }
@@ -0,0 +1,258 @@
+/// Scheduling of puzzle unlocks, used by `cargo today --wait`.
+/// Puzzles unlock at midnight server time, from the 1st of december to the last day of the event.
+/// Time is read through a [`Clock`], so that the scheduling can be tested without waiting for it.
+use std::{fmt::Display, thread, time::Duration};
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::{Day, last_day};
+
+/// Longest wait between two attempts of [`retry`].
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unlock {
+    pub year: i32,
+    pub day: Day,
+    pub at: DateTime<Utc>,
+}
+
+/// The next puzzle to unlock after `now`.
+pub fn next_unlock(now: DateTime<Utc>) -> Unlock {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+    let today = now.with_timezone(&offset);
+
+    let (year, day) = match (today.month(), today.day()) {
+        (12, day) if day < u32::from(last_day(today.year()).into_inner()) => {
+            (today.year(), day + 1)
+        }
+        (12, _) => (today.year() + 1, 1),
+        _ => (today.year(), 1),
+    };
+
+    Unlock {
+        year,
+        day: Day::new(u8::try_from(day).unwrap()).unwrap(),
+        at: offset
+            .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc),
+    }
+}
+
+/// Sleep until `at`, calling `tick` with the remaining time on every whole second.
+pub fn wait_until(clock: &impl Clock, at: DateTime<Utc>, mut tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = (at - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        tick(remaining);
+
+        // sleep to the next whole second, so the countdown ticks in step with the unlock.
+        let step = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(u64::from(nanos)),
+        };
+        clock.sleep(step.min(remaining));
+    }
+}
+
+/// Formats a countdown as `HH:MM:SS`, prefixed with the number of days if it is longer than one.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    );
+
+    match secs / 86_400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+/// Wait before the next attempt after `attempt` failed ones: 1s, 2s, 4s, … up to a minute.
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << (attempt.clamp(1, 7) - 1)).min(MAX_BACKOFF)
+}
+
+/// Call `func` until it succeeds, fails with an error that is not retriable, or failed `attempts` times.
+pub fn retry<T, E: Display>(
+    clock: &impl Clock,
+    attempts: u32,
+    is_retriable: impl Fn(&E) -> bool,
+    mut func: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match func() {
+            Err(e) if attempt < attempts && is_retriable(&e) => {
+                let wait = backoff(attempt);
+                eprintln!("{e} Retrying in {wait:?}…");
+                clock.sleep(wait);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "test_lib", feature = "today"))]
+mod tests {
+    use super::{Clock, backoff, format_countdown, next_unlock, retry, wait_until};
+    use crate::day;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    struct MockClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl MockClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            MockClock {
+                now: Cell::new(now),
+                sleeps: RefCell::default(),
+            }
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, hour, min, sec)
+            .unwrap()
+    }
+
+    #[test]
+    fn finds_the_next_unlock() {
+        // puzzles unlock at 05:00 UTC.
+        let unlock = next_unlock(utc(11, 30, 12, 0, 0));
+        assert_eq!((unlock.year, unlock.day), (2024, day!(1)));
+        assert_eq!(unlock.at, utc(12, 1, 5, 0, 0));
+
+        let unlock = next_unlock(utc(12, 6, 4, 59, 59));
+        assert_eq!((unlock.day, unlock.at), (day!(6), utc(12, 6, 5, 0, 0)));
+
+        let unlock = next_unlock(utc(12, 6, 5, 0, 0));
+        assert_eq!((unlock.day, unlock.at), (day!(7), utc(12, 7, 5, 0, 0)));
+
+        let unlock = next_unlock(utc(12, 25, 5, 0, 0));
+        assert_eq!((unlock.year, unlock.day), (2025, day!(1)));
+
+        // since 2025, the event ends after the 12th.
+        let unlock = next_unlock(Utc.with_ymd_and_hms(2025, 12, 12, 5, 0, 0).unwrap());
+        assert_eq!((unlock.year, unlock.day), (2026, day!(1)));
+        assert_eq!(
+            unlock.at,
+            Utc.with_ymd_and_hms(2026, 12, 1, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn waits_in_whole_seconds() {
+        let clock = MockClock::at(utc(12, 1, 4, 59, 57) - Duration::from_millis(250));
+        let mut ticks = vec![];
+        wait_until(&clock, utc(12, 1, 5, 0, 0), |remaining| {
+            ticks.push(format_countdown(remaining));
+        });
+
+        assert_eq!(clock.now(), utc(12, 1, 5, 0, 0));
+        assert_eq!(ticks, vec!["00:00:04", "00:00:03", "00:00:02", "00:00:01"]);
+        assert_eq!(clock.sleeps.borrow()[0], Duration::from_millis(250));
+
+        // an unlock in the past does not wait at all.
+        wait_until(&clock, utc(12, 1, 4, 0, 0), |_| panic!("should not tick"));
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 3600 + 61)),
+            "03:01:01"
+        );
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86_400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = MockClock::at(utc(12, 1, 5, 0, 0));
+        let mut calls = 0;
+        let result: Result<u8, String> = retry(
+            &clock,
+            5,
+            |_| true,
+            || {
+                calls += 1;
+                if calls < 4 {
+                    Err("not yet.".into())
+                } else {
+                    Ok(42)
+                }
+            },
+        );
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![backoff(1), backoff(2), backoff(3)]
+        );
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+        assert_eq!(backoff(20), Duration::from_secs(60));
+
+        let result: Result<u8, String> = retry(&clock, 3, |_| true, || Err("down.".into()));
+        assert!(result.is_err());
+
+        let mut calls = 0;
+        let result: Result<u8, String> = retry(
+            &clock,
+            3,
+            |_| false,
+            || {
+                calls += 1;
+                Err("fatal.".into())
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,168 @@
+/// Watch mode of `cargo solve`: polls the files a day depends on and rebuilds and reruns it on every change.
+/// Polling keeps this dependency-free and working on any filesystem.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::protocol::{PARSE_PART, PartRecord};
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification time and size of every watched file.
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Rerun a day whenever its solution, its input or examples, or the template change. Runs until interrupted.
+pub fn watch(day: Day, is_release: bool, options: &RunOptions) -> ! {
+    let mut snapshot = take_snapshot(day, &options.input);
+    let mut previous: Vec<PartRecord> = vec![];
+
+    loop {
+        print!("{CLEAR_SCREEN}");
+        println!(
+            "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, ctrl+c to exit){ANSI_RESET}"
+        );
+        println!("------");
+
+        // the day binary is rebuilt by `cargo run`, compile errors are forwarded to stderr.
+        let records = child_commands::run_solution(day, options, is_release).unwrap_or_else(|e| {
+            eprintln!("Failed to run day {day}: {e:?}");
+            vec![]
+        });
+
+        let changes = compare_answers(&previous, &records);
+        if !changes.is_empty() {
+            println!();
+            println!("{ANSI_BOLD}Previous run{ANSI_RESET}");
+            changes.iter().for_each(|line| println!("{line}"));
+        }
+
+        // keep the last answers around while the solution does not build.
+        if !records.is_empty() {
+            previous = records;
+        }
+
+        snapshot = wait_for_change(day, &options.input, &snapshot);
+    }
+}
+
+/// Lines comparing the answers of the previous run with the current one. Empty for the first run.
+fn compare_answers(previous: &[PartRecord], current: &[PartRecord]) -> Vec<String> {
+    if previous.is_empty() {
+        return vec![];
+    }
+
+    current
+        .iter()
+        .filter(|r| r.part != PARSE_PART)
+        .map(|record| {
+            let answer = describe(record);
+            match previous
+                .iter()
+                .find(|r| r.part == record.part)
+                .map(describe)
+            {
+                Some(before) if before == answer => {
+                    format!("Part {}: {answer} (unchanged)", record.part)
+                }
+                Some(before) => format!("Part {}: {before} → {answer}", record.part),
+                None => format!("Part {}: {answer} (new)", record.part),
+            }
+        })
+        .collect()
+}
+
+fn describe(record: &PartRecord) -> String {
+    match (&record.answer, &record.error) {
+        (Some(answer), _) => answer.clone(),
+        (None, Some(error)) => error.clone(),
+        (None, None) => "✖".into(),
+    }
+}
+
+fn wait_for_change(day: Day, input: &InputSource, snapshot: &Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if take_snapshot(day, input) != *snapshot {
+            // editors tend to save in several steps, give them a moment to settle.
+            thread::sleep(POLL_INTERVAL);
+            return take_snapshot(day, input);
+        }
+    }
+}
+
+fn take_snapshot(day: Day, input: &InputSource) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let is_day_file = |path: &Path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&day.to_string()))
+    };
+
+    add_path(&mut snapshot, Path::new(&get_path_for_bin(day)), &|_| true);
+    add_path(&mut snapshot, Path::new("./data/inputs"), &is_day_file);
+    add_path(&mut snapshot, Path::new("./data/examples"), &is_day_file);
+    add_path(&mut snapshot, Path::new("./src/template"), &|_| true);
+
+    if let InputSource::Path(path) = input {
+        add_path(&mut snapshot, path, &|_| true);
+    }
+
+    snapshot
+}
+
+fn add_path(snapshot: &mut Snapshot, path: &Path, filter: &dyn Fn(&Path) -> bool) {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            add_path(snapshot, &entry.path(), filter);
+        }
+    } else if filter(path)
+        && let Ok(meta) = fs::metadata(path)
+    {
+        snapshot.insert(path.to_path_buf(), (meta.modified().ok(), meta.len()));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare_answers;
+    use crate::template::fixtures::record;
+    use crate::template::protocol::{PartRecord, Status};
+
+    #[test]
+    fn skips_comparison_on_first_run() {
+        assert!(compare_answers(&[], &[record(1, 1000.0, Status::Solved)]).is_empty());
+    }
+
+    #[test]
+    fn compares_answers_with_previous_run() {
+        let previous = [
+            record(0, 1000.0, Status::Unsolved),
+            record(1, 1000.0, Status::Solved),
+            record(2, 1000.0, Status::Unsolved),
+        ];
+        let current = [
+            record(0, 1000.0, Status::Unsolved),
+            record(1, 1000.0, Status::Solved),
+            PartRecord {
+                answer: Some("7".into()),
+                ..record(2, 1000.0, Status::Solved)
+            },
+        ];
+
+        assert_eq!(
+            compare_answers(&previous, &current),
+            vec!["Part 1: 42 (unchanged)", "Part 2: ✖ → 7"]
+        );
+    }
+}